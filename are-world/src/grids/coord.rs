@@ -1,4 +1,7 @@
 //! by *StarvinCulex @2021/10/24*
+
+use serde::{Deserialize, Serialize};
+
 /// 可以表示二维平面中位置信息的数对结构
 ///
 /// # 如何构造
@@ -33,7 +36,15 @@
 /// * [`contains`]
 /// * [`contains_point`]
 /// * [`offset`]
-#[derive(Hash, Debug)]
+/// * [`expand`]
+///
+/// 以及把`Coord<Interval<isize>>`看作环面上区域的方法
+/// * [`area_len`]
+/// * [`intersect`]
+/// * [`subtract`]
+/// * [`bounding_union`]
+/// * [`split_at_seams`]
+#[derive(Hash, Debug, Serialize, Deserialize)]
 pub struct Coord<T>(pub T, pub T);
 
 #[allow(dead_code)]
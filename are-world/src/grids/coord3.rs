@@ -0,0 +1,239 @@
+//! 三维的[`Coord`]
+
+use serde::{Deserialize, Serialize};
+
+use super::{coord::Coord, interval::Interval};
+
+/// 可以表示三维空间中位置信息的三元组，用法同[`Coord`]
+///
+/// 三个数依次是 _**x**_、_**y**_、_**z**_，
+/// `Coord3(x, y, z).xy()`是所在层中的二维位置。
+///
+/// 比较大小时使用积序：每个维度都不小于对方才算不小于，见[`Coord`]的实现。
+///
+/// 和[`Interval`]混合使用时，`Coord3<Interval<T>>`表示三维的区间，
+/// 用`|`运算符把两个[`Coord3<T>`]组合成一个[`Coord3<Interval<T>>`]。
+#[derive(Hash, Debug, Serialize, Deserialize)]
+pub struct Coord3<T>(pub T, pub T, pub T);
+
+#[allow(dead_code)]
+impl<T> Coord3<T> {
+    /// ```rust
+    /// return Coord3(f(self.0), f(self.1), f(self.2));
+    #[inline]
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Coord3<U> {
+        Coord3(f(self.0), f(self.1), f(self.2))
+    }
+    /// ```rust
+    /// return Coord3(f(self.0, rhs.0), f(self.1, rhs.1), f(self.2, rhs.2));
+    #[inline]
+    pub fn reduce<U, V>(self, rhs: Coord3<U>, f: impl Fn(T, U) -> V) -> Coord3<V> {
+        Coord3(f(self.0, rhs.0), f(self.1, rhs.1), f(self.2, rhs.2))
+    }
+    /// ```rust
+    /// return f(f(self.0, self.1), self.2);
+    #[inline]
+    pub fn merge(self, f: impl Fn(T, T) -> T) -> T {
+        f(f(self.0, self.1), self.2)
+    }
+    /// ```rust
+    /// return Coord(self.0, self.1);
+    #[inline]
+    pub fn xy(self) -> Coord<T> {
+        Coord(self.0, self.1)
+    }
+    /// ```rust
+    /// return Coord3(xy.0, xy.1, z);
+    #[inline]
+    pub fn from_xy(xy: Coord<T>, z: T) -> Self {
+        Coord3(xy.0, xy.1, z)
+    }
+}
+
+impl<T, R> std::ops::Add<Coord3<R>> for Coord3<T>
+where
+    T: std::ops::Add<R>,
+{
+    type Output = Coord3<<T as std::ops::Add<R>>::Output>;
+    #[inline]
+    fn add(self, rhs: Coord3<R>) -> Self::Output {
+        Coord3(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
+impl<T, R> std::ops::Sub<Coord3<R>> for Coord3<T>
+where
+    T: std::ops::Sub<R>,
+{
+    type Output = Coord3<<T as std::ops::Sub<R>>::Output>;
+    #[inline]
+    fn sub(self, rhs: Coord3<R>) -> Self::Output {
+        Coord3(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
+    }
+}
+
+impl<T, R> std::ops::Mul<Coord3<R>> for Coord3<T>
+where
+    T: std::ops::Mul<R>,
+{
+    type Output = Coord3<<T as std::ops::Mul<R>>::Output>;
+    #[inline]
+    fn mul(self, rhs: Coord3<R>) -> Self::Output {
+        Coord3(self.0 * rhs.0, self.1 * rhs.1, self.2 * rhs.2)
+    }
+}
+
+impl<T, R> std::ops::Div<Coord3<R>> for Coord3<T>
+where
+    T: std::ops::Div<R>,
+{
+    type Output = Coord3<<T as std::ops::Div<R>>::Output>;
+    #[inline]
+    fn div(self, rhs: Coord3<R>) -> Self::Output {
+        Coord3(self.0 / rhs.0, self.1 / rhs.1, self.2 / rhs.2)
+    }
+}
+
+impl<T, R> std::ops::Rem<Coord3<R>> for Coord3<T>
+where
+    T: std::ops::Rem<R>,
+{
+    type Output = Coord3<<T as std::ops::Rem<R>>::Output>;
+    #[inline]
+    fn rem(self, rhs: Coord3<R>) -> Self::Output {
+        Coord3(self.0 % rhs.0, self.1 % rhs.1, self.2 % rhs.2)
+    }
+}
+
+impl<T: std::ops::Neg> std::ops::Neg for Coord3<T> {
+    type Output = Coord3<<T as std::ops::Neg>::Output>;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Coord3(-self.0, -self.1, -self.2)
+    }
+}
+
+impl<T: Clone> Clone for Coord3<T> {
+    fn clone(&self) -> Self {
+        Coord3(self.0.clone(), self.1.clone(), self.2.clone())
+    }
+}
+
+impl<T: Copy> Copy for Coord3<T> {}
+
+impl<T, R> PartialEq<Coord3<R>> for Coord3<T>
+where
+    T: PartialEq<R>,
+{
+    #[inline]
+    fn eq(&self, rhs: &Coord3<R>) -> bool {
+        self.0 == rhs.0 && self.1 == rhs.1 && self.2 == rhs.2
+    }
+}
+
+impl<T: PartialEq + Eq> Eq for Coord3<T> {}
+
+impl<T, R> PartialOrd<Coord3<R>> for Coord3<T>
+where
+    T: PartialOrd<R>,
+{
+    fn partial_cmp(&self, rhs: &Coord3<R>) -> std::option::Option<std::cmp::Ordering> {
+        use std::cmp::Ordering;
+        let mut result = Ordering::Equal;
+        for ordering in [
+            self.0.partial_cmp(&rhs.0)?,
+            self.1.partial_cmp(&rhs.1)?,
+            self.2.partial_cmp(&rhs.2)?,
+        ] {
+            match (result, ordering) {
+                (_, Ordering::Equal) => {}
+                (Ordering::Equal, _) => result = ordering,
+                (a, b) if a != b => return None,
+                _ => {}
+            }
+        }
+        Some(result)
+    }
+}
+
+impl<T: Copy> From<T> for Coord3<T> {
+    fn from(t: T) -> Self {
+        Coord3(t, t, t)
+    }
+}
+
+impl<T: Default> Default for Coord3<T> {
+    fn default() -> Self {
+        Coord3(T::default(), T::default(), T::default())
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Coord3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({0}, {1}, {2})", self.0, self.1, self.2)
+    }
+}
+
+impl<T> std::ops::BitOr for Coord3<T>
+where
+    T: Ord,
+{
+    type Output = Coord3<Interval<T>>;
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Coord3(
+            Interval::new(self.0, rhs.0),
+            Interval::new(self.1, rhs.1),
+            Interval::new(self.2, rhs.2),
+        )
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Coord3<Interval<T>>
+where
+    T: Ord,
+{
+    /// ```rust
+    /// return Coord3(self.0.from, self.1.from, self.2.from);
+    #[inline]
+    pub fn from(self) -> Coord3<T> {
+        Coord3(self.0.from, self.1.from, self.2.from)
+    }
+    /// ```rust
+    /// return Coord3(self.0.to, self.1.to, self.2.to);
+    #[inline]
+    pub fn to(self) -> Coord3<T> {
+        Coord3(self.0.to, self.1.to, self.2.to)
+    }
+    /// 判断`point`是否属于`self`表示的范围
+    #[inline]
+    pub fn contains(&self, point: &Coord3<T>) -> bool {
+        self.0.contains(&point.0) && self.1.contains(&point.1) && self.2.contains(&point.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_product_order() {
+        let a = Coord3(1, 2, 3);
+        assert!(a <= Coord3(1, 2, 3));
+        assert!(a < Coord3(1, 2, 4));
+        assert!(a < Coord3(2, 3, 4));
+        assert!(a > Coord3(0, 2, 3));
+        assert_eq!(a.partial_cmp(&Coord3(0, 3, 3)), None);
+        assert_eq!(
+            a.partial_cmp(&Coord3(1, 2, 2)),
+            Some(std::cmp::Ordering::Greater)
+        );
+        assert_eq!(a.partial_cmp(&Coord3(2, 2, 2)), None);
+        assert_eq!(
+            (Coord3(1, 2, 3) - Coord3(3, 2, 1)) * Coord3(2, 2, 2),
+            Coord3(-4, 0, 4)
+        );
+        assert_eq!((Coord3(0, 0, 0) | Coord3(1, 2, 3)).to().xy(), Coord(1, 2));
+    }
+}
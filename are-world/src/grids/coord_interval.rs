@@ -1,6 +1,6 @@
 //! by *StarvinCulex @2021/10/24*
 
-use super::{coord::Coord, interval::Interval};
+use super::{coord::Coord, interval::Interval, interval_set::IntervalSet};
 
 impl<T> std::ops::BitOr for Coord<T>
 where
@@ -59,8 +59,198 @@ where
     <T as std::ops::Sub<T>>::Output: Into<<T as std::ops::Add<T>>::Output>,
     <T as std::ops::Add>::Output: std::cmp::Ord,
 {
+    /// | 原值 | 返回值 |
+    /// |:---:|:-----:|
+    /// |`([p->q], [r->s])`|`([p-rhs.0->q+rhs.0], [r-rhs.1->s+rhs.1])`|
     #[inline]
     pub fn expand(self, rhs: Coord<T>) -> Coord<Interval<<T as std::ops::Add>::Output>> {
-        self.reduce(rhs, Interval::offset)
+        self.reduce(rhs, Interval::expand)
+    }
+}
+
+/// 以下方法把`Coord<Interval<isize>>`看作`size`大小的环面上的区域，
+/// 区间的两端都应该已经标准化，见[`Matrix::normalize_area`](super::Matrix::normalize_area)。
+///
+/// 返回多个区域的方法，返回的区域之间互不相交。
+#[allow(dead_code)]
+impl Coord<Interval<isize>> {
+    /// 区域包含多少个格子
+    #[inline]
+    pub fn area_len(self, size: Coord<isize>) -> isize {
+        size.reduce(self, |s, i| i.len(s)).merge(std::ops::Mul::mul)
+    }
+
+    /// 在`self`中也在`other`中的格子，最多四个区域
+    pub fn intersect(self, other: Self, size: Coord<isize>) -> Vec<Self> {
+        let xs = self.0.intersect(other.0, size.0);
+        let ys = self.1.intersect(other.1, size.1);
+        Self::product(&xs, &ys)
+    }
+
+    /// 在`self`中但不在`other`中的格子，最多六个区域
+    pub fn subtract(self, other: Self, size: Coord<isize>) -> Vec<Self> {
+        let x = self.0.to_set(size.0);
+        let y = self.1.to_set(size.1);
+        let other_x = other.0.to_set(size.0);
+        let other_y = other.1.to_set(size.1);
+        // (X - A) × Y ∪ (X ∩ A) × (Y - B)
+        let mut areas = Self::product(&x.difference(&other_x), &y);
+        areas.extend(Self::product(
+            &x.intersect(&other_x),
+            &y.difference(&other_y),
+        ));
+        areas
+    }
+
+    /// 包含`self`和`other`的最小区域
+    pub fn bounding_union(self, other: Self, size: Coord<isize>) -> Self {
+        let x = self.0.union(other.0, size.0).bounding().unwrap();
+        let y = self.1.union(other.1, size.1).bounding().unwrap();
+        Coord(x, y)
+    }
+
+    /// 在上下、左右的边界处切开，得到最多四个不跨过边界的区域，
+    /// 按[`Scan`](super::Scan)的遍历顺序分块排列
+    pub fn split_at_seams(self, size: Coord<isize>) -> Vec<Self> {
+        let mut areas = vec![];
+        for y in self.1.to_ranges(size.1) {
+            for x in self.0.to_ranges(size.0) {
+                areas.push(Coord(
+                    Interval::new(*x.start(), *x.end()),
+                    Interval::new(*y.start(), *y.end()),
+                ));
+            }
+        }
+        areas
+    }
+}
+
+// private
+impl Coord<Interval<isize>> {
+    fn product(xs: &IntervalSet, ys: &IntervalSet) -> Vec<Self> {
+        let mut areas = vec![];
+        for y in ys.pieces() {
+            for x in xs.pieces() {
+                areas.push(Coord(x, y));
+            }
+        }
+        areas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZES: [Coord<isize>; 5] = [
+        Coord(1, 1),
+        Coord(1, 3),
+        Coord(2, 2),
+        Coord(3, 2),
+        Coord(3, 3),
+    ];
+
+    fn all_areas(size: Coord<isize>) -> Vec<Coord<Interval<isize>>> {
+        let mut areas = vec![];
+        for y0 in 0..size.1 {
+            for y1 in 0..size.1 {
+                for x0 in 0..size.0 {
+                    for x1 in 0..size.0 {
+                        areas.push(Coord(x0, y0) | Coord(x1, y1));
+                    }
+                }
+            }
+        }
+        areas
+    }
+
+    /// 第`x + y * size.0`位表示格子`(x, y)`是否在区域中
+    fn cells(area: Coord<Interval<isize>>, size: Coord<isize>) -> u64 {
+        let mut bits = 0;
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                if area.contains(&Coord(x, y)) {
+                    bits |= 1 << (x + y * size.0);
+                }
+            }
+        }
+        bits
+    }
+
+    /// 检查`areas`互不相交，返回它们的并集
+    fn disjoint_cells(areas: &[Coord<Interval<isize>>], size: Coord<isize>) -> u64 {
+        let mut bits = 0;
+        for area in areas {
+            let c = cells(*area, size);
+            assert_eq!(bits & c, 0);
+            assert_eq!(c.count_ones() as isize, area.area_len(size));
+            bits |= c;
+        }
+        bits
+    }
+
+    #[test]
+    fn test_area_len_and_split() {
+        for size in SIZES {
+            for area in all_areas(size) {
+                let c = cells(area, size);
+                assert_eq!(area.area_len(size), c.count_ones() as isize);
+                let pieces = area.split_at_seams(size);
+                assert!(pieces.len() <= 4);
+                for piece in &pieces {
+                    assert!(piece.0.from <= piece.0.to && piece.1.from <= piece.1.to);
+                }
+                assert_eq!(disjoint_cells(&pieces, size), c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersect_subtract() {
+        for size in SIZES {
+            let areas = all_areas(size);
+            for a in &areas {
+                for b in &areas {
+                    let (x, y) = (cells(*a, size), cells(*b, size));
+
+                    let intersect = a.intersect(*b, size);
+                    assert!(intersect.len() <= 4);
+                    assert_eq!(disjoint_cells(&intersect, size), x & y);
+
+                    let subtract = a.subtract(*b, size);
+                    assert!(subtract.len() <= 6);
+                    assert_eq!(disjoint_cells(&subtract, size), x & !y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounding_union() {
+        for size in SIZES {
+            let areas = all_areas(size);
+            for a in &areas {
+                for b in &areas {
+                    let union = cells(*a, size) | cells(*b, size);
+                    let bounding = a.bounding_union(*b, size);
+                    assert_eq!(cells(bounding, size) & union, union);
+                    let smallest = areas
+                        .iter()
+                        .filter(|c| cells(**c, size) & union == union)
+                        .map(|c| c.area_len(size))
+                        .min()
+                        .unwrap();
+                    assert_eq!(bounding.area_len(size), smallest);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(
+            (Coord(2, 3) | Coord(4, 5)).expand(Coord(1, 2)),
+            Coord(1, 1) | Coord(5, 7)
+        );
     }
 }
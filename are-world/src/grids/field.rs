@@ -0,0 +1,169 @@
+//! 多源距离场和流场
+//!
+//! 大量单位朝最近的目标移动时，不必每个单位都寻一次路：
+//! 先用[`distance_field`]从所有目标出发做一次广度优先搜索，
+//! 再用[`flow_field`]得到每个格子应该往哪个方向走。
+
+use std::collections::VecDeque;
+
+use super::{Coord, Matrix, Neighborhood};
+
+/// 从`sources`中所有位置同时出发做广度优先搜索，
+/// 返回每个格子到最近的源需要走的步数。
+///
+/// 只能经过`passable(pos, element)`为`true`的格子，源本身总是可以经过。
+/// 走不到的格子是[`None`]。
+#[allow(dead_code)]
+pub fn distance_field<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+    matrix: &Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+    sources: impl IntoIterator<Item = Coord<isize>>,
+    neighborhood: Neighborhood,
+    passable: impl Fn(Coord<isize>, &Element) -> bool,
+) -> Matrix<Option<u32>, CHUNK_WIDTH, CHUNK_HEIGHT> {
+    let size = *matrix.size();
+    let mut field = Matrix::<Option<u32>, CHUNK_WIDTH, CHUNK_HEIGHT>::new(&Coord(
+        size.0 as usize,
+        size.1 as usize,
+    ));
+    let mut queue = VecDeque::new();
+
+    for source in sources {
+        let source = matrix.normalize(source);
+        if field[source].is_none() {
+            field[source] = Some(0);
+            queue.push_back(source);
+        }
+    }
+
+    while let Some(pos) = queue.pop_front() {
+        let distance = field[pos].unwrap() + 1;
+        for offset in neighborhood.offsets() {
+            let next = matrix.normalize(pos + *offset);
+            if field[next].is_none() && passable(next, &matrix[next]) {
+                field[next] = Some(distance);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    field
+}
+
+/// 根据[`distance_field`]的结果，为每个格子选出走向最近的源的方向。
+///
+/// 返回值是相对于格子的偏移量：
+/// - 源是`Coord(0, 0)`
+/// - 走不到源的格子是[`None`]
+/// - 其余的格子是距离最小的邻格方向，距离相同时取[`Neighborhood::offsets`]中靠前的
+#[allow(dead_code)]
+pub fn flow_field<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+    field: &Matrix<Option<u32>, CHUNK_WIDTH, CHUNK_HEIGHT>,
+    neighborhood: Neighborhood,
+) -> Matrix<Option<Coord<isize>>, CHUNK_WIDTH, CHUNK_HEIGHT> {
+    let size = *field.size();
+    Matrix::with_ctor(&Coord(size.0 as usize, size.1 as usize), |opt_pos| {
+        let pos = opt_pos?;
+        let distance = field[pos]?;
+        if distance == 0 {
+            return Some(Coord(0, 0));
+        }
+        let mut best: Option<(u32, Coord<isize>)> = None;
+        for offset in neighborhood.offsets() {
+            if let Some(d) = field[pos + *offset] {
+                if d < distance && best.is_none_or(|(b, _)| d < b) {
+                    best = Some((d, *offset));
+                }
+            }
+        }
+        best.map(|(_, offset)| offset)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type M = Matrix<bool, 3, 2>;
+
+    #[test]
+    fn test_distance_wraps() {
+        let matrix = M::with_fill(&Coord(7, 5), &true);
+        let field = distance_field(&matrix, [Coord(0, 0)], Neighborhood::Four, |_, e| *e);
+        assert_eq!(field[Coord(6, 4)], Some(2));
+        assert_eq!(field[Coord(3, 2)], Some(5));
+        assert_eq!(field[Coord(4, 0)], Some(3));
+
+        let field = distance_field(&matrix, [Coord(0, 0)], Neighborhood::Eight, |_, e| *e);
+        assert_eq!(field[Coord(6, 4)], Some(1));
+        assert_eq!(field[Coord(3, 2)], Some(3));
+    }
+
+    #[test]
+    fn test_multiple_sources_and_walls() {
+        // 第2列是墙
+        let matrix = M::with_ctor(&Coord(6, 4), |opt_pos| {
+            !matches!(opt_pos, Some(Coord(2, _)))
+        });
+        let field = distance_field(
+            &matrix,
+            [Coord(0, 0), Coord(-2, 1), Coord(6, 4)],
+            Neighborhood::Four,
+            |_, e| *e,
+        );
+        for (pos, passable) in matrix.iter() {
+            if !passable {
+                assert_eq!(field[pos], None);
+            }
+        }
+        assert_eq!(field[Coord(0, 0)], Some(0));
+        assert_eq!(field[Coord(4, 1)], Some(0));
+        assert_eq!(field[Coord(3, 3)], Some(3));
+        assert_eq!(field[Coord(1, 2)], Some(3));
+
+        let isolated = M::with_ctor(&Coord(6, 4), |opt_pos| {
+            !matches!(opt_pos, Some(Coord(2, _)) | Some(Coord(4, _)))
+        });
+        let field = distance_field(&isolated, [Coord(0, 0)], Neighborhood::Eight, |_, e| *e);
+        assert_eq!(field[Coord(3, 1)], None);
+        assert_eq!(field[Coord(5, 1)], Some(1));
+    }
+
+    #[test]
+    fn test_flow_reaches_source() {
+        let matrix = M::with_ctor(&Coord(9, 6), |opt_pos| {
+            !matches!(opt_pos, Some(Coord(4, 1..)))
+        });
+        let sources = [Coord(1, 1), Coord(7, 4)];
+        for n in [Neighborhood::Four, Neighborhood::Eight] {
+            let field = distance_field(&matrix, sources, n, |_, e| *e);
+            let flow = flow_field(&field, n);
+            for (start, _) in matrix.iter() {
+                let mut pos = start;
+                match field[pos] {
+                    None => assert_eq!(flow[pos], None),
+                    Some(distance) => {
+                        for _ in 0..distance {
+                            let step = flow[pos].unwrap();
+                            assert!(n.offsets().contains(&step));
+                            pos = matrix.normalize(pos + step);
+                        }
+                        assert!(sources.contains(&pos));
+                        assert_eq!(flow[pos], Some(Coord(0, 0)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_flow_tie_break() {
+        let matrix = M::with_fill(&Coord(5, 5), &true);
+        let field = distance_field(&matrix, [Coord(0, 0)], Neighborhood::Four, |_, e| *e);
+        let flow = flow_field(&field, Neighborhood::Four);
+        // (1, 1)向上和向左距离相同，取offsets中靠前的向上
+        assert_eq!(flow[Coord(1, 1)], Some(Coord(0, -1)));
+        assert_eq!(flow[Coord(4, 4)], Some(Coord(1, 0)));
+        let again = flow_field(&field, Neighborhood::Four);
+        assert!(flow.iter().all(|(pos, dir)| again[pos] == *dir));
+    }
+}
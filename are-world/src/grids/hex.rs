@@ -0,0 +1,174 @@
+//! 六边形网格的轴坐标
+//!
+//! 六边形网格存在[`Matrix`](super::Matrix)中时使用“奇数行右移”（odd-r）的偏移坐标，
+//! 也就是普通的`Coord<isize>`：奇数行的格子比偶数行向右错开半格。
+//! 轴坐标[`Axial`]便于计算邻居、距离和直线，两种坐标可以互相转换。
+//!
+//! 在环面上，上下边界相连处的行错位必须一致，所以矩阵的高度必须是偶数。
+
+use serde::{Deserialize, Serialize};
+
+use super::Coord;
+
+/// 六边形网格的轴坐标`(q, r)`
+///
+/// `r`和偏移坐标的行号相同，`q`沿着行向右增加，
+/// 第三个隐含的坐标是`-q - r`。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub struct Axial(pub isize, pub isize);
+
+#[allow(dead_code)]
+impl Axial {
+    /// 六个方向，从右边开始逆时针排列：右、右上、左上、左、左下、右下
+    pub const DIRECTIONS: [Axial; 6] = [
+        Axial(1, 0),
+        Axial(1, -1),
+        Axial(0, -1),
+        Axial(-1, 0),
+        Axial(-1, 1),
+        Axial(0, 1),
+    ];
+
+    /// 从odd-r偏移坐标转换
+    #[inline]
+    pub const fn from_offset(pos: Coord<isize>) -> Self {
+        Axial(pos.0 - (pos.1 - (pos.1 & 1)) / 2, pos.1)
+    }
+
+    /// 转换成odd-r偏移坐标
+    #[inline]
+    pub const fn to_offset(self) -> Coord<isize> {
+        Coord(self.0 + (self.1 - (self.1 & 1)) / 2, self.1)
+    }
+
+    /// 相邻的六个格子，顺序同[`Axial::DIRECTIONS`]
+    #[inline]
+    pub fn neighbors(self) -> [Axial; 6] {
+        Self::DIRECTIONS.map(|d| self + d)
+    }
+
+    /// 从原点走到`self`的步数
+    #[inline]
+    pub fn steps(self) -> isize {
+        self.0.abs().max(self.1.abs()).max((self.0 + self.1).abs())
+    }
+
+    /// `self`和`other`之间的步数
+    #[inline]
+    pub fn distance(self, other: Self) -> isize {
+        (other - self).steps()
+    }
+
+    /// 从`self`到`other`的直线经过的格子，包括两端，相邻两个格子相邻。
+    ///
+    /// 直线恰好经过两个格子的边界时，取向右、向下偏的格子。
+    pub fn line(self, other: Self) -> Vec<Axial> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![self];
+        }
+        // 稍微偏移起点，避免直线恰好落在边界上
+        const NUDGE: f64 = 1e-6;
+        let from = (self.0 as f64 + NUDGE, self.1 as f64 + NUDGE);
+        let to = (other.0 as f64 + NUDGE, other.1 as f64 + NUDGE);
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                Self::round(from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
+            })
+            .collect()
+    }
+
+    /// 离小数坐标`(q, r)`最近的格子
+    fn round(q: f64, r: f64) -> Self {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Axial(rq as isize, rr as isize)
+    }
+}
+
+impl std::ops::Add for Axial {
+    type Output = Axial;
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Axial(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl std::ops::Sub for Axial {
+    type Output = Axial;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Axial(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl std::ops::Mul<isize> for Axial {
+    type Output = Axial;
+    #[inline]
+    fn mul(self, rhs: isize) -> Self::Output {
+        Axial(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl From<Coord<isize>> for Axial {
+    #[inline]
+    fn from(pos: Coord<isize>) -> Self {
+        Self::from_offset(pos)
+    }
+}
+
+impl From<Axial> for Coord<isize> {
+    #[inline]
+    fn from(hex: Axial) -> Self {
+        hex.to_offset()
+    }
+}
+
+impl std::fmt::Display for Axial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}, {}>", self.0, self.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_roundtrip() {
+        for y in -5..5 {
+            for x in -5..5 {
+                let pos = Coord(x, y);
+                assert_eq!(Axial::from_offset(pos).to_offset(), pos);
+            }
+        }
+        assert_eq!(Axial::from_offset(Coord(3, 3)), Axial(2, 3));
+        assert_eq!(Axial::from_offset(Coord(3, -1)), Axial(4, -1));
+    }
+
+    #[test]
+    fn test_line() {
+        for a in [Axial(0, 0), Axial(2, -3), Axial(-1, 4)] {
+            for b in [Axial(0, 0), Axial(5, -2), Axial(-3, -3), Axial(1, 6)] {
+                let line = a.line(b);
+                assert_eq!(line.len() as isize, a.distance(b) + 1);
+                assert_eq!(line.first(), Some(&a));
+                assert_eq!(line.last(), Some(&b));
+                for pair in line.windows(2) {
+                    assert_eq!(pair[0].distance(pair[1]), 1);
+                }
+            }
+        }
+        assert_eq!(
+            Axial(0, 0).line(Axial(3, 0)),
+            vec![Axial(0, 0), Axial(1, 0), Axial(2, 0), Axial(3, 0)]
+        );
+    }
+}
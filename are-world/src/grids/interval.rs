@@ -2,6 +2,8 @@
 
 use std::fmt::Formatter;
 
+use serde::{Deserialize, Serialize};
+
 /// 表示一维范围
 ///
 /// 包含字段`from`和`to`两个`<T>`类型的字段
@@ -15,7 +17,7 @@ use std::fmt::Formatter;
 /// 当`from > to`时：
 /// - 表示小于等于`from`或大于等于`to`的范围
 /// - 区间表示：**(-∞, from] ∪ [to, +∞)**
-#[derive(Hash, Debug)]
+#[derive(Hash, Debug, Serialize, Deserialize)]
 pub struct Interval<T>
 where
     T: Ord,
@@ -77,9 +79,10 @@ where
     <T as std::ops::Sub<T>>::Output: Into<<T as std::ops::Add<T>>::Output>,
     <T as std::ops::Add>::Output: std::cmp::Ord,
 {
+    /// 将`self.from`减去`rhs`、`self.to`加上`rhs`得到的值
     #[inline]
     pub fn expand(self, rhs: T) -> Interval<<T as std::ops::Add<T>>::Output> {
-        Interval::new(self.from + rhs.clone(), (self.to - rhs).into())
+        Interval::new((self.from - rhs.clone()).into(), self.to + rhs)
     }
 }
 
@@ -94,6 +97,8 @@ where
     }
 }
 
+impl<T: Eq + Ord> Eq for Interval<T> {}
+
 impl<T> Clone for Interval<T>
 where
     T: Clone + Ord,
@@ -0,0 +1,397 @@
+//! 环形区间的集合运算
+//!
+//! [`Interval`]用`from > to`表示跨过边界的区间，
+//! 两个这样的区间求交、并、差以后可能会变成两段，
+//! 所以结果用[`IntervalSet`]表示。
+
+use std::ops::RangeInclusive;
+
+use super::{measure_length, Interval};
+
+/// `0..modulus`的环上若干个点组成的集合
+///
+/// 内部保存互不相交、互不相邻、从小到大排列的闭区间，
+/// 每个区间都满足`0 <= from <= to < modulus`。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct IntervalSet {
+    modulus: isize,
+    ranges: Vec<Interval<isize>>,
+}
+
+#[allow(dead_code)]
+impl IntervalSet {
+    /// 空集
+    #[inline]
+    pub fn empty(modulus: isize) -> Self {
+        Self {
+            modulus,
+            ranges: vec![],
+        }
+    }
+
+    /// 整个环
+    #[inline]
+    pub fn full(modulus: isize) -> Self {
+        Self {
+            modulus,
+            ranges: vec![Interval::new(0, modulus - 1)],
+        }
+    }
+
+    /// `interval`包含的所有点。`interval`的两端会先对`modulus`取模
+    pub fn from_interval(interval: Interval<isize>, modulus: isize) -> Self {
+        let from = interval.from.rem_euclid(modulus);
+        let to = interval.to.rem_euclid(modulus);
+        let ranges = if from <= to {
+            vec![Interval::new(from, to)]
+        } else {
+            vec![Interval::new(0, to), Interval::new(from, modulus - 1)]
+        };
+        Self::normalized(modulus, ranges)
+    }
+
+    #[inline]
+    pub const fn modulus(&self) -> isize {
+        self.modulus
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.modulus
+    }
+
+    /// 集合中点的个数
+    #[inline]
+    pub fn len(&self) -> isize {
+        self.ranges.iter().map(|r| r.to - r.from + 1).sum()
+    }
+
+    /// 判断`point`是否属于集合。`point`会先对`modulus`取模
+    pub fn contains(&self, point: isize) -> bool {
+        let point = point.rem_euclid(self.modulus);
+        self.ranges.iter().any(|r| r.contains(&point))
+    }
+
+    /// 把集合表示成尽量少的[`Interval`]。
+    ///
+    /// 同时包含`0`和`modulus - 1`时，两端的区间合并成一个`from > to`的区间，放在最后。
+    pub fn pieces(&self) -> Vec<Interval<isize>> {
+        let mut pieces = self.ranges.clone();
+        if pieces.len() >= 2 {
+            let first = pieces[0];
+            let last = pieces[pieces.len() - 1];
+            if first.from == 0 && last.to == self.modulus - 1 {
+                pieces.remove(0);
+                pieces.pop();
+                pieces.push(Interval::new(last.from, first.to));
+            }
+        }
+        pieces
+    }
+
+    /// 从小到大排列的、不跨过边界的区间
+    #[inline]
+    pub fn to_ranges(&self) -> Vec<RangeInclusive<isize>> {
+        self.ranges.iter().map(|r| r.from..=r.to).collect()
+    }
+
+    /// 不在集合中的点
+    pub fn complement(&self) -> Self {
+        let mut ranges = vec![];
+        let mut start = 0;
+        for r in &self.ranges {
+            if start < r.from {
+                ranges.push(Interval::new(start, r.from - 1));
+            }
+            start = r.to + 1;
+        }
+        if start < self.modulus {
+            ranges.push(Interval::new(start, self.modulus - 1));
+        }
+        Self {
+            modulus: self.modulus,
+            ranges,
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        debug_assert_eq!(self.modulus, other.modulus);
+        let ranges = self
+            .ranges
+            .iter()
+            .chain(other.ranges.iter())
+            .copied()
+            .collect();
+        Self::normalized(self.modulus, ranges)
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        debug_assert_eq!(self.modulus, other.modulus);
+        let mut ranges = vec![];
+        for a in &self.ranges {
+            for b in &other.ranges {
+                let from = std::cmp::max(a.from, b.from);
+                let to = std::cmp::min(a.to, b.to);
+                if from <= to {
+                    ranges.push(Interval::new(from, to));
+                }
+            }
+        }
+        Self::normalized(self.modulus, ranges)
+    }
+
+    /// 在`self`中但不在`other`中的点
+    #[inline]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersect(&other.complement())
+    }
+
+    #[inline]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// 包含集合中所有点的最短的一个区间，空集返回[`None`]。
+    ///
+    /// 去掉最长的一段空隙得到；整个环都在集合中时返回`[0->modulus-1]`。
+    pub fn bounding(&self) -> Option<Interval<isize>> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut longest: Option<Interval<isize>> = None;
+        for gap in self.complement().pieces() {
+            if longest.is_none_or(|l| l.len(self.modulus) < gap.len(self.modulus)) {
+                longest = Some(gap);
+            }
+        }
+        Some(match longest {
+            None => Interval::new(0, self.modulus - 1),
+            Some(gap) => Interval::new(
+                (gap.to + 1).rem_euclid(self.modulus),
+                (gap.from - 1).rem_euclid(self.modulus),
+            ),
+        })
+    }
+}
+
+// private
+impl IntervalSet {
+    /// 排序并合并相交或相邻的区间
+    fn normalized(modulus: isize, mut ranges: Vec<Interval<isize>>) -> Self {
+        ranges.sort_by_key(|r| r.from);
+        let mut merged: Vec<Interval<isize>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.from <= last.to + 1 => last.to = std::cmp::max(last.to, r.to),
+                _ => merged.push(r),
+            }
+        }
+        Self {
+            modulus,
+            ranges: merged,
+        }
+    }
+}
+
+impl std::fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pieces = self.pieces();
+        if pieces.is_empty() {
+            return write!(f, "∅");
+        }
+        for (i, piece) in pieces.iter().enumerate() {
+            if i != 0 {
+                write!(f, "∪")?;
+            }
+            write!(f, "{}", piece)?;
+        }
+        Ok(())
+    }
+}
+
+/// 以下方法把`Interval`看作`0..modulus`的环上的区间，
+/// `from`和`to`都应该在`0..modulus`之间。
+#[allow(dead_code)]
+impl Interval<isize> {
+    /// 区间包含多少个点，和[`measure_length`]相同
+    #[inline]
+    pub fn len(self, modulus: isize) -> isize {
+        measure_length(modulus, self)
+    }
+
+    /// 拆成不跨过边界的区间，按从`from`开始遍历的顺序排列，最多两个
+    pub fn to_ranges(self, modulus: isize) -> Vec<RangeInclusive<isize>> {
+        if self.from <= self.to {
+            vec![self.from..=self.to]
+        } else {
+            vec![self.from..=modulus - 1, 0..=self.to]
+        }
+    }
+
+    #[inline]
+    pub fn to_set(self, modulus: isize) -> IntervalSet {
+        IntervalSet::from_interval(self, modulus)
+    }
+
+    #[inline]
+    pub fn intersect(self, other: Self, modulus: isize) -> IntervalSet {
+        self.to_set(modulus).intersect(&other.to_set(modulus))
+    }
+
+    #[inline]
+    pub fn union(self, other: Self, modulus: isize) -> IntervalSet {
+        self.to_set(modulus).union(&other.to_set(modulus))
+    }
+
+    /// 在`self`中但不在`other`中的点
+    #[inline]
+    pub fn difference(self, other: Self, modulus: isize) -> IntervalSet {
+        self.to_set(modulus).difference(&other.to_set(modulus))
+    }
+
+    #[inline]
+    pub fn overlaps(self, other: Self, modulus: isize) -> bool {
+        self.to_set(modulus).overlaps(&other.to_set(modulus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 第`i`位表示点`i`是否在集合中
+    fn bits_of_interval(interval: Interval<isize>, modulus: isize) -> u32 {
+        (0..modulus)
+            .filter(|p| interval.contains(p))
+            .fold(0, |bits, p| bits | 1 << p)
+    }
+
+    fn bits_of_set(set: &IntervalSet) -> u32 {
+        (0..set.modulus())
+            .filter(|p| set.contains(*p))
+            .fold(0, |bits, p| bits | 1 << p)
+    }
+
+    fn check_normalized(set: &IntervalSet) {
+        let modulus = set.modulus();
+        assert_eq!(set.len(), bits_of_set(set).count_ones() as isize);
+        for w in set.ranges.windows(2) {
+            assert!(w[0].to + 1 < w[1].from);
+        }
+        for r in &set.ranges {
+            assert!(0 <= r.from && r.from <= r.to && r.to < modulus);
+        }
+        let pieces = set.pieces();
+        assert!(pieces.len() <= set.ranges.len());
+        let bits = pieces
+            .iter()
+            .fold(0, |bits, p| bits | bits_of_interval(*p, modulus));
+        assert_eq!(bits, bits_of_set(set));
+        let bits = set
+            .to_ranges()
+            .into_iter()
+            .flatten()
+            .fold(0, |bits, p| bits | 1 << p);
+        assert_eq!(bits, bits_of_set(set));
+    }
+
+    fn all_intervals(modulus: isize) -> Vec<Interval<isize>> {
+        (0..modulus)
+            .flat_map(|from| (0..modulus).map(move |to| Interval::new(from, to)))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval_len_and_ranges() {
+        for modulus in 1..=7 {
+            for interval in all_intervals(modulus) {
+                let bits = bits_of_interval(interval, modulus);
+                assert_eq!(interval.len(modulus), bits.count_ones() as isize);
+                let ranges = interval.to_ranges(modulus);
+                assert!(ranges.len() <= 2);
+                assert_eq!(*ranges[0].start(), interval.from);
+                let covered = ranges.into_iter().flatten().fold(0, |b, p| b | 1 << p);
+                assert_eq!(covered, bits);
+
+                let set = interval.to_set(modulus);
+                check_normalized(&set);
+                assert_eq!(bits_of_set(&set), bits);
+                assert_eq!(set.complement().len(), modulus - set.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        for modulus in 1..=6 {
+            let full = (1u32 << modulus) - 1;
+            for a in all_intervals(modulus) {
+                for b in all_intervals(modulus) {
+                    let (x, y) = (bits_of_interval(a, modulus), bits_of_interval(b, modulus));
+
+                    let intersect = a.intersect(b, modulus);
+                    check_normalized(&intersect);
+                    assert_eq!(bits_of_set(&intersect), x & y);
+                    assert!(intersect.pieces().len() <= 2);
+
+                    let union = a.union(b, modulus);
+                    check_normalized(&union);
+                    assert_eq!(bits_of_set(&union), x | y);
+                    assert!(union.pieces().len() <= 2);
+
+                    let difference = a.difference(b, modulus);
+                    check_normalized(&difference);
+                    assert_eq!(bits_of_set(&difference), x & !y & full);
+                    assert!(difference.pieces().len() <= 2);
+
+                    assert_eq!(a.overlaps(b, modulus), x & y != 0);
+                    assert_eq!(union.is_full(), x | y == full);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounding() {
+        for modulus in 1..=6 {
+            for a in all_intervals(modulus) {
+                for b in all_intervals(modulus) {
+                    let union = a.union(b, modulus);
+                    let bounding = union.bounding().unwrap();
+                    assert_eq!(
+                        bits_of_interval(bounding, modulus) & bits_of_set(&union),
+                        bits_of_set(&union)
+                    );
+                    let shortest = all_intervals(modulus)
+                        .into_iter()
+                        .filter(|c| c.to_set(modulus).intersect(&union) == union)
+                        .map(|c| c.len(modulus))
+                        .min()
+                        .unwrap();
+                    assert_eq!(bounding.len(modulus), shortest);
+                }
+            }
+        }
+        assert_eq!(IntervalSet::empty(5).bounding(), None);
+    }
+
+    #[test]
+    fn test_pieces() {
+        let set = Interval::new(7, 2).to_set(10);
+        assert_eq!(set.pieces(), vec![Interval::new(7, 2)]);
+        assert_eq!(set.to_ranges(), vec![0..=2, 7..=9]);
+        assert_eq!(set.to_string(), "[7->2]");
+
+        let set = set.union(&Interval::new(4, 5).to_set(10));
+        assert_eq!(set.pieces(), vec![Interval::new(4, 5), Interval::new(7, 2)]);
+        assert_eq!(set.to_string(), "[4->5]∪[7->2]");
+
+        assert_eq!(Interval::new(5, 4).to_set(10), IntervalSet::full(10));
+        assert_eq!(IntervalSet::empty(10).to_string(), "∅");
+    }
+}
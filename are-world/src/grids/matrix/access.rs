@@ -1,13 +1,98 @@
 // by *StarvinCulex @2021/11/14*
 
-pub trait Accessor<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+/// 按某种顺序访问矩阵中格子的访问器。
+/// [`Iterator`]不做边界检查，直接使用访问器给出的偏移量。
+///
+/// 在库外定义的形状应该实现[`Shape`]，由[`Checked`]计算偏移量。
+///
+/// # Safety
+/// 实现者必须保证[`next`](Accessor::next)返回的每个`(pos, addr)`中，
+/// `pos`已经标准化，并且`addr`正是`pos`在[`matrix_size`](Accessor::matrix_size)大小、
+/// `CHUNK_WIDTH`×`CHUNK_HEIGHT`分块的矩阵中的偏移量。
+#[allow(dead_code)]
+pub unsafe trait Accessor<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    /// 返回下一个格子的位置和它在矩阵中的偏移量
     fn next(&mut self) -> Option<(Coord<isize>, usize)>;
 
+    /// 剩余的格子数
     fn len(&self) -> usize;
 
+    /// 包含所有格子的区域
     fn super_area(&self) -> Coord<Interval<isize>>;
 
+    /// 判断`pos`是否是访问的格子之一，`pos`已经标准化
     fn contains(&self, pos: Coord<isize>) -> bool;
 
     fn r#type(&self) -> &'static str;
+
+    /// 访问的矩阵的大小
+    fn matrix_size(&self) -> Coord<isize>;
+
+    /// 先访问`self`中的格子，再访问`other`中不属于`self`的格子，每个格子只访问一次。
+    ///
+    /// 构造时会复制并遍历一遍`self`和`other`来计算长度
+    #[inline]
+    fn union<B>(self, other: B) -> Union<Self, B>
+    where
+        Self: Sized + Clone,
+        B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> + Clone,
+    {
+        Union::new(self, other)
+    }
+
+    /// 只访问`self`中也属于`other`的格子，每个格子只访问一次。
+    ///
+    /// 构造时会复制并遍历一遍`self`来计算长度
+    #[inline]
+    fn intersection<B>(self, other: B) -> Intersection<Self, B>
+    where
+        Self: Sized + Clone,
+        B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    {
+        Intersection::new(self, other)
+    }
+
+    /// 只访问`predicate(pos)`为`true`的格子。
+    ///
+    /// 构造时会复制并遍历一遍`self`来计算长度
+    #[inline]
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized + Clone,
+        P: Fn(Coord<isize>) -> bool,
+    {
+        Filter::new(self, predicate)
+    }
+
+    /// 只访问相对于`super_area().from()`的位移是`stride`整数倍的格子。
+    ///
+    /// 构造时会复制并遍历一遍`self`来计算长度
+    #[inline]
+    fn step_by(self, stride: Coord<isize>) -> StepBy<Self>
+    where
+        Self: Sized + Clone,
+    {
+        StepBy::new(self, stride)
+    }
+
+    /// 先访问`self`中的格子，再访问`other`中的格子，不去重
+    #[inline]
+    fn chain<B>(self, other: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+        B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    {
+        Chain::new(self, other)
+    }
+}
+
+/// 可以从两端访问的访问器
+///
+/// # Safety
+/// [`next_back`](DoubleEndedAccessor::next_back)的返回值和[`Accessor::next`]有相同的约定。
+pub unsafe trait DoubleEndedAccessor<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>:
+    Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 返回最后一个格子的位置和它在矩阵中的偏移量
+    fn next_back(&mut self) -> Option<(Coord<isize>, usize)>;
 }
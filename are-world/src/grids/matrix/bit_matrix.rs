@@ -0,0 +1,379 @@
+/// 每个格子只占一位的`Matrix<bool>`
+///
+/// 和[`Matrix`]一样按块存储、按环面取下标，第`i`位对应[`Matrix`]中偏移量是`i`的格子。
+/// 不使用的位总是`0`，所以可以直接按字比较和计数。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitMatrix<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    words: Vec<u64>,
+    size: Coord<isize>,
+}
+
+#[allow(dead_code)]
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> BitMatrix<CHUNK_WIDTH, CHUNK_HEIGHT> {
+    /// 构造大小为`size`、所有格子都是`false`的矩阵
+    ///
+    /// *不能构造的时候panic，见[`BitMatrix::try_new`]*
+    pub fn new(size: &Coord<usize>) -> Self {
+        Self::try_new(size).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// 同[`BitMatrix::new`]，错误见[`Matrix::try_with_ctor`]
+    pub fn try_new(size: &Coord<usize>) -> Result<Self, MatrixError> {
+        let alloc_size = Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::check_alloc_size(*size)?;
+        Ok(Self {
+            words: vec![0; alloc_size.div_ceil(64)],
+            size: Coord(size.0 as isize, size.1 as isize),
+        })
+    }
+
+    /// 构造大小为`size`的矩阵，`predicate(pos)`为`true`的格子是`true`
+    pub fn with_ctor(size: &Coord<usize>, mut predicate: impl FnMut(Coord<isize>) -> bool) -> Self {
+        let mut instance = Self::new(size);
+        for y in 0..instance.size.1 {
+            for x in 0..instance.size.0 {
+                if predicate(Coord(x, y)) {
+                    instance.set(Coord(x, y), true);
+                }
+            }
+        }
+        instance
+    }
+
+    /// 按`matrix`中的值构造
+    pub fn from_matrix(matrix: &Matrix<bool, CHUNK_WIDTH, CHUNK_HEIGHT>) -> Self {
+        Self::from_predicate(matrix, |_, value| *value)
+    }
+
+    /// 构造和`matrix`大小相同的矩阵，`predicate(pos, element)`为`true`的格子是`true`
+    pub fn from_predicate<Element>(
+        matrix: &Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+        mut predicate: impl FnMut(Coord<isize>, &Element) -> bool,
+    ) -> Self {
+        let size = *matrix.size();
+        let mut instance = Self::new(&Coord(size.0 as usize, size.1 as usize));
+        for (pos, element) in matrix.iter() {
+            if predicate(pos, element) {
+                instance.set(pos, true);
+            }
+        }
+        instance
+    }
+
+    pub fn to_matrix(&self) -> Matrix<bool, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        Matrix::with_ctor(
+            &Coord(self.size.0 as usize, self.size.1 as usize),
+            |opt_pos| opt_pos.is_some_and(|pos| self.get(pos)),
+        )
+    }
+
+    #[inline]
+    pub const fn size(&self) -> &Coord<isize> {
+        &self.size
+    }
+
+    #[inline]
+    pub fn normalize(&self, pos: Coord<isize>) -> Coord<isize> {
+        Torus::new(self.size).normalize(pos)
+    }
+
+    /// `pos`处的值，`pos`会先标准化
+    #[inline]
+    pub fn get(&self, pos: Coord<isize>) -> bool {
+        let addr = self.addr(pos);
+        self.words[addr / 64] & (1 << (addr % 64)) != 0
+    }
+
+    /// 把`pos`处改为`value`，`pos`会先标准化
+    #[inline]
+    pub fn set(&mut self, pos: Coord<isize>, value: bool) {
+        let addr = self.addr(pos);
+        if value {
+            self.words[addr / 64] |= 1 << (addr % 64);
+        } else {
+            self.words[addr / 64] &= !(1 << (addr % 64));
+        }
+    }
+
+    /// 按行遍历`area`中每个格子的值
+    pub fn area(
+        &self,
+        area: Coord<Interval<isize>>,
+    ) -> impl std::iter::Iterator<Item = (Coord<isize>, bool)> + '_ {
+        let mut scan = Scan::<CHUNK_WIDTH, CHUNK_HEIGHT>::new(
+            self.size,
+            self.normalize(area.from()) | self.normalize(area.to()),
+        );
+        std::iter::from_fn(move || {
+            let (pos, addr) = scan.next()?;
+            Some((pos, self.words[addr / 64] & (1 << (addr % 64)) != 0))
+        })
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl std::iter::Iterator<Item = (Coord<isize>, bool)> + '_ {
+        self.area(Coord(0, 0) | (self.size - Coord(1, 1)))
+    }
+
+    /// 所有`true`的格子，按在内存中的顺序排列
+    pub fn ones(&self) -> impl std::iter::Iterator<Item = Coord<isize>> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, word)| {
+            let mut rest = *word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                // SAFETY: 不使用的位总是`0`，所以这一位对应矩阵中的格子
+                Some(unsafe {
+                    Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::pos_at_unchecked(
+                        self.size,
+                        i * 64 + bit,
+                    )
+                })
+            })
+        })
+    }
+
+    /// `true`的格子数
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// 大小不同时返回[`MatrixError::SizeMismatch`]
+    #[inline]
+    pub fn and(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.zip_words(other, |a, b| a & b)
+    }
+
+    /// 大小不同时返回[`MatrixError::SizeMismatch`]
+    #[inline]
+    pub fn or(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.zip_words(other, |a, b| a | b)
+    }
+
+    /// 大小不同时返回[`MatrixError::SizeMismatch`]
+    #[inline]
+    pub fn xor(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.zip_words(other, |a, b| a ^ b)
+    }
+
+    pub fn not(&self) -> Self {
+        let mut result = Self {
+            words: self.words.iter().map(|word| !word).collect(),
+            size: self.size,
+        };
+        result.clear_padding();
+        result
+    }
+
+    /// 所有格子移动`offset`，从一边移出的格子从另一边移入
+    pub fn shift(&self, offset: Coord<isize>) -> Self {
+        let mut result = Self::new(&Coord(self.size.0 as usize, self.size.1 as usize));
+        for pos in self.ones() {
+            result.set(pos + offset, true);
+        }
+        result
+    }
+
+    #[inline]
+    fn addr(&self, pos: Coord<isize>) -> usize {
+        Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address(self.size, pos)
+    }
+
+    fn zip_words(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Result<Self, MatrixError> {
+        check_size(self.size, other.size)?;
+        Ok(Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| f(*a, *b))
+                .collect(),
+            size: self.size,
+        })
+    }
+
+    /// 把不使用的位改回`0`
+    fn clear_padding(&mut self) {
+        let usize_size = Coord(self.size.0 as usize, self.size.1 as usize);
+        // `self.size`在构造时检查过
+        let alloc_size = Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::check_alloc_size(usize_size)
+            .unwrap_or_else(|error| panic!("{}", error));
+        // 最后一个字中超出数组的位
+        if !alloc_size.is_multiple_of(64) {
+            *self.words.last_mut().unwrap() &= (1 << (alloc_size % 64)) - 1;
+        }
+        // 只有最右一列和最下一行的块中有不使用的格子
+        let chunk_size = Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_chunk_size(usize_size);
+        let padded = Coord(
+            (chunk_size.0 * CHUNK_WIDTH) as isize,
+            (chunk_size.1 * CHUNK_HEIGHT) as isize,
+        );
+        let mut clear = |pos: Coord<isize>| {
+            // SAFETY: `pos`在分配的块之内
+            let addr = unsafe {
+                Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address_unchecked(self.size, pos)
+            };
+            self.words[addr / 64] &= !(1 << (addr % 64));
+        };
+        for y in 0..padded.1 {
+            for x in self.size.0..padded.0 {
+                clear(Coord(x, y));
+            }
+        }
+        for y in self.size.1..padded.1 {
+            for x in 0..self.size.0 {
+                clear(Coord(x, y));
+            }
+        }
+    }
+}
+
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::Index<Coord<isize>>
+    for BitMatrix<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, index: Coord<isize>) -> &bool {
+        if self.get(index) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+#[cfg(test)]
+mod bit_matrix_tests {
+    use super::*;
+
+    fn random<const CW: usize, const CH: usize>(
+        size: Coord<usize>,
+        rng: &mut Pcg32,
+    ) -> Matrix<bool, CW, CH> {
+        Matrix::with_ctor(&size, |opt_pos| opt_pos.is_some() && rng.chance(0.4))
+    }
+
+    fn assert_same<const CW: usize, const CH: usize>(
+        bits: &BitMatrix<CW, CH>,
+        expected: &Matrix<bool, CW, CH>,
+    ) {
+        assert_eq!(bits.size(), expected.size());
+        for (pos, value) in expected.iter() {
+            assert_eq!(bits[pos], *value, "{}", pos);
+        }
+        assert_eq!(bits.count_ones(), expected.count_where(|_, v| *v));
+    }
+
+    #[test]
+    fn test_invalid_size() {
+        assert_eq!(
+            BitMatrix::<2, 3>::try_new(&Coord(0, 3)),
+            Err(MatrixError::ZeroSize {
+                size: Coord(0, 3).into()
+            })
+        );
+        assert_eq!(
+            BitMatrix::<0, 3>::try_new(&Coord(1, 1)),
+            Err(MatrixError::ZeroChunkSize {
+                chunk: Coord(0, 3).into()
+            })
+        );
+        let huge = Coord(isize::MAX as usize, 2);
+        assert_eq!(
+            BitMatrix::<2, 3>::try_new(&huge),
+            Err(MatrixError::AllocationOverflow { size: huge.into() })
+        );
+        assert_eq!(
+            BitMatrix::<2, 3>::try_new(&Coord(3, 4))
+                .unwrap()
+                .count_ones(),
+            0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "matrix size must not be zero")]
+    fn test_zero_size_panics() {
+        BitMatrix::<2, 3>::new(&Coord(0, 3));
+    }
+
+    fn test_sub<const CW: usize, const CH: usize>() {
+        let mut rng = Pcg32::new(CW as u64, CH as u64);
+        for size in [Coord(1, 1), Coord(5, 3), Coord(9, 7), Coord(17, 4)] {
+            let a = random::<CW, CH>(size, &mut rng);
+            let b = random::<CW, CH>(size, &mut rng);
+            let bits_a = BitMatrix::from_matrix(&a);
+            let bits_b = BitMatrix::from_matrix(&b);
+            assert_same(&bits_a, &a);
+            let combine = |f: fn(bool, bool) -> bool| {
+                Matrix::<bool, CW, CH>::with_ctor(&size, |opt_pos| {
+                    opt_pos.is_some_and(|pos| f(a[pos], b[pos]))
+                })
+            };
+            assert_same(&bits_a.and(&bits_b).unwrap(), &combine(|x, y| x && y));
+            assert_same(&bits_a.or(&bits_b).unwrap(), &combine(|x, y| x || y));
+            assert_same(&bits_a.xor(&bits_b).unwrap(), &combine(|x, y| x != y));
+            assert_same(&bits_a.not(), &combine(|x, _| !x));
+            assert_eq!(bits_a.not().not(), bits_a);
+
+            let offset = Coord(-3, 5);
+            let shifted = bits_a.shift(offset);
+            for (pos, value) in a.iter() {
+                assert_eq!(shifted[pos + offset], *value);
+            }
+            assert_eq!(shifted.shift(Coord(3, -5)), bits_a);
+
+            let mut ones: Vec<_> = bits_a.ones().collect();
+            ones.sort_by_key(|pos| (pos.1, pos.0));
+            assert_eq!(ones, a.positions(|_, v| *v).collect::<Vec<_>>());
+
+            let area = Coord(-1, 2) | Coord(1, 0);
+            assert!(bits_a
+                .area(area)
+                .zip(a.area(area))
+                .all(|((p, x), (q, y))| p == q && x == *y));
+            let back = bits_a.to_matrix();
+            assert!(a.iter().all(|(pos, value)| back[pos] == *value));
+        }
+    }
+
+    #[test]
+    fn test_against_matrix() {
+        test_sub::<1, 1>();
+        test_sub::<2, 3>();
+        test_sub::<4, 4>();
+        test_sub::<8, 8>();
+        test_sub::<64, 1>();
+    }
+
+    #[test]
+    fn test_construct_and_errors() {
+        let matrix = Matrix::<u8, 2, 2>::with_ctor(&Coord(4, 3), |opt_pos| {
+            opt_pos.map_or(0, |pos| (pos.0 * pos.1) as u8)
+        });
+        let even = BitMatrix::from_predicate(&matrix, |_, v| *v % 2 == 0);
+        let expected = BitMatrix::with_ctor(&Coord(4, 3), |pos| (pos.0 * pos.1) % 2 == 0);
+        assert_eq!(even, expected);
+        assert_eq!(even.count_ones(), 10);
+
+        let mut other = BitMatrix::<2, 2>::new(&Coord(3, 4));
+        other.set(Coord(-1, -1), true);
+        assert!(other[Coord(2, 3)]);
+        other.set(Coord(2, 3), false);
+        assert_eq!(other.count_ones(), 0);
+        assert_eq!(
+            even.and(&other),
+            Err(MatrixError::SizeMismatch {
+                expected: Coord(4, 3),
+                found: Coord(3, 4)
+            })
+        );
+    }
+}
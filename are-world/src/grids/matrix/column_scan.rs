@@ -0,0 +1,92 @@
+/// 按列遍历区域的访问器，见[`Matrix::column_scan`]
+#[derive(Clone)]
+pub struct ColumnScan<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    at: Coord<isize>,
+    back: Coord<isize>,
+    length: usize,
+
+    area: Coord<Interval<isize>>,
+
+    matrix_size: Coord<isize>,
+}
+
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> ColumnScan<CHUNK_WIDTH, CHUNK_HEIGHT> {
+    /// `area`必须已经标准化
+    #[inline]
+    fn new(matrix_size: Coord<isize>, area: Coord<Interval<isize>>) -> Self {
+        Self {
+            at: area.from(),
+            back: area.to(),
+            length: measure_area(matrix_size, area).merge(std::ops::Mul::mul) as usize,
+            area,
+            matrix_size,
+        }
+    }
+}
+
+// SAFETY: `area`已经标准化，遍历时的位置都在矩阵之内
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>
+    for ColumnScan<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    #[inline]
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        if self.length == 0 {
+            return None;
+        }
+        let at = self.at;
+        let addr = unsafe {
+            Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address_unchecked(self.matrix_size, at)
+        };
+        self.length -= 1;
+        self.at = if at.1 == self.area.1.to {
+            Coord(wrapping_inc(at.0, self.matrix_size.0), self.area.1.from)
+        } else {
+            Coord(at.0, wrapping_inc(at.1, self.matrix_size.1))
+        };
+        Some((at, addr))
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        self.area
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.area.contains(&pos)
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MColumnScan"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.matrix_size
+    }
+}
+
+// SAFETY: 同`Accessor`
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    DoubleEndedAccessor<CHUNK_WIDTH, CHUNK_HEIGHT> for ColumnScan<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<(Coord<isize>, usize)> {
+        if self.length == 0 {
+            return None;
+        }
+        let at = self.back;
+        let addr = unsafe {
+            Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address_unchecked(self.matrix_size, at)
+        };
+        self.length -= 1;
+        self.back = if at.1 == self.area.1.from {
+            Coord(wrapping_dec(at.0, self.matrix_size.0), self.area.1.to)
+        } else {
+            Coord(at.0, wrapping_dec(at.1, self.matrix_size.1))
+        };
+        Some((at, addr))
+    }
+}
@@ -0,0 +1,578 @@
+/// 见[`Accessor::union`]
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct Union<A, B> {
+    first: A,
+    second: B,
+    /// 已经访问过的格子
+    seen: Seen,
+    length: usize,
+}
+
+#[allow(dead_code)]
+impl<A, B> Union<A, B> {
+    fn new<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(first: A, second: B) -> Self
+    where
+        A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> + Clone,
+        B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> + Clone,
+    {
+        assert_same_matrix(&first, &second);
+        let seen = Seen::new(first.matrix_size());
+        let mut counted = seen.clone();
+        let length = count_where(&first, |pos| counted.insert(pos))
+            + count_where(&second, |pos| counted.insert(pos));
+        Self {
+            first,
+            second,
+            seen,
+            length,
+        }
+    }
+}
+
+// SAFETY: 只转发内部访问器给出的偏移量
+unsafe impl<A, B, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> for Union<A, B>
+where
+    A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+{
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        loop {
+            let (pos, addr) = match self.first.next() {
+                Some(item) => item,
+                None => self.second.next()?,
+            };
+            if self.seen.insert(pos) {
+                self.length -= 1;
+                return Some((pos, addr));
+            }
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        self.first
+            .super_area()
+            .bounding_union(self.second.super_area(), self.matrix_size())
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.first.contains(pos) || self.second.contains(pos)
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MUnion"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.first.matrix_size()
+    }
+}
+
+/// 见[`Accessor::intersection`]
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct Intersection<A, B> {
+    first: A,
+    second: B,
+    /// 已经访问过的格子
+    seen: Seen,
+    length: usize,
+}
+
+#[allow(dead_code)]
+impl<A, B> Intersection<A, B> {
+    fn new<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(first: A, second: B) -> Self
+    where
+        A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> + Clone,
+        B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    {
+        assert_same_matrix(&first, &second);
+        let seen = Seen::new(first.matrix_size());
+        let mut counted = seen.clone();
+        let length = count_where(&first, |pos| second.contains(pos) && counted.insert(pos));
+        Self {
+            first,
+            second,
+            seen,
+            length,
+        }
+    }
+}
+
+// SAFETY: 只转发内部访问器给出的偏移量
+unsafe impl<A, B, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> for Intersection<A, B>
+where
+    A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+{
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        loop {
+            let (pos, addr) = self.first.next()?;
+            if self.second.contains(pos) && self.seen.insert(pos) {
+                self.length -= 1;
+                return Some((pos, addr));
+            }
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        let size = self.matrix_size();
+        let first = self.first.super_area();
+        first
+            .intersect(self.second.super_area(), size)
+            .into_iter()
+            .reduce(|a, b| a.bounding_union(b, size))
+            .unwrap_or(first)
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.first.contains(pos) && self.second.contains(pos)
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MIntersection"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.first.matrix_size()
+    }
+}
+
+/// 见[`Accessor::filter`]
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct Filter<A, P> {
+    inner: A,
+    predicate: P,
+    length: usize,
+}
+
+#[allow(dead_code)]
+impl<A, P> Filter<A, P>
+where
+    P: Fn(Coord<isize>) -> bool,
+{
+    fn new<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(inner: A, predicate: P) -> Self
+    where
+        A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> + Clone,
+    {
+        let length = count_where(&inner, &predicate);
+        Self {
+            inner,
+            predicate,
+            length,
+        }
+    }
+}
+
+// SAFETY: 只转发内部访问器给出的偏移量
+unsafe impl<A, P, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> for Filter<A, P>
+where
+    A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    P: Fn(Coord<isize>) -> bool,
+{
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        loop {
+            let (pos, addr) = self.inner.next()?;
+            if (self.predicate)(pos) {
+                self.length -= 1;
+                return Some((pos, addr));
+            }
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        self.inner.super_area()
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.inner.contains(pos) && (self.predicate)(pos)
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MFilter"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.inner.matrix_size()
+    }
+}
+
+/// 见[`Accessor::step_by`]
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct StepBy<A> {
+    inner: A,
+    origin: Coord<isize>,
+    stride: Coord<isize>,
+    torus: Torus,
+    length: usize,
+}
+
+#[allow(dead_code)]
+impl<A> StepBy<A> {
+    fn new<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+        inner: A,
+        stride: Coord<isize>,
+    ) -> Self
+    where
+        A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> + Clone,
+    {
+        assert!(stride.0 > 0 && stride.1 > 0, "stride must be positive");
+        let mut instance = Self {
+            origin: inner.super_area().from(),
+            stride,
+            torus: Torus::new(inner.matrix_size()),
+            inner,
+            length: 0,
+        };
+        instance.length = count_where(&instance.inner, |pos| instance.on_grid(pos));
+        instance
+    }
+
+    #[inline]
+    fn on_grid(&self, pos: Coord<isize>) -> bool {
+        let offset = self.torus.normalize(pos - self.origin);
+        offset.0 % self.stride.0 == 0 && offset.1 % self.stride.1 == 0
+    }
+}
+
+// SAFETY: 只转发内部访问器给出的偏移量
+unsafe impl<A, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> for StepBy<A>
+where
+    A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+{
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        loop {
+            let (pos, addr) = self.inner.next()?;
+            if self.on_grid(pos) {
+                self.length -= 1;
+                return Some((pos, addr));
+            }
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        self.inner.super_area()
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.inner.contains(pos) && self.on_grid(pos)
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MStepBy"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.inner.matrix_size()
+    }
+}
+
+/// 见[`Accessor::chain`]
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+#[allow(dead_code)]
+impl<A, B> Chain<A, B> {
+    fn new<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(first: A, second: B) -> Self
+    where
+        A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+        B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    {
+        assert_same_matrix(&first, &second);
+        Self { first, second }
+    }
+}
+
+// SAFETY: 只转发内部访问器给出的偏移量
+unsafe impl<A, B, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> for Chain<A, B>
+where
+    A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+{
+    #[inline]
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        self.first
+            .super_area()
+            .bounding_union(self.second.super_area(), self.matrix_size())
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.first.contains(pos) || self.second.contains(pos)
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MChain"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.first.matrix_size()
+    }
+}
+
+#[allow(dead_code)]
+#[inline]
+fn assert_same_matrix<A, B, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(a: &A, b: &B)
+where
+    A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    B: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+{
+    assert_eq!(
+        a.matrix_size(),
+        b.matrix_size(),
+        "accessors were created for matrices of different sizes"
+    );
+}
+
+/// 数出`accessor`剩下的格子中有多少个满足`predicate`，不改变`accessor`
+#[allow(dead_code)]
+fn count_where<A, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+    accessor: &A,
+    mut predicate: impl FnMut(Coord<isize>) -> bool,
+) -> usize
+where
+    A: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> + Clone,
+{
+    let mut rest = accessor.clone();
+    let mut count = 0;
+    while let Some((pos, _)) = rest.next() {
+        if predicate(pos) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// 按位置记录访问过的格子，每个格子一位
+#[allow(dead_code)]
+#[derive(Clone)]
+struct Seen {
+    size: Coord<isize>,
+    words: Vec<u64>,
+}
+
+#[allow(dead_code)]
+impl Seen {
+    fn new(size: Coord<isize>) -> Self {
+        Self {
+            size,
+            words: vec![0; ((size.0 * size.1) as usize).div_ceil(64)],
+        }
+    }
+
+    /// 记下`pos`，之前没有记过时返回`true`
+    #[inline]
+    fn insert(&mut self, pos: Coord<isize>) -> bool {
+        let index =
+            (pos.1.rem_euclid(self.size.1) * self.size.0 + pos.0.rem_euclid(self.size.0)) as usize;
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        let new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        new
+    }
+}
+
+#[cfg(test)]
+mod combine_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    type M = Matrix<String, 2, 3>;
+
+    fn matrix() -> M {
+        M::with_ctor(&Coord(7, 5), |opt_pos| {
+            opt_pos.map_or("".to_string(), |pos| pos.to_string())
+        })
+    }
+
+    /// 检查遍历结果和`contains`、`len`、`super_area`一致，返回访问到的格子
+    fn check<Access: Accessor<2, 3>>(matrix: &M, accessor: Access) -> Vec<Coord<isize>> {
+        let size = *matrix.size();
+        let super_area = accessor.super_area();
+        let expected: Vec<_> = matrix
+            .iter()
+            .map(|(pos, _)| pos)
+            .filter(|pos| accessor.contains(*pos))
+            .collect();
+        let mut iter = matrix.select(accessor);
+        let total = std::iter::ExactSizeIterator::len(&iter);
+        let mut visited = vec![];
+        loop {
+            let remaining = std::iter::ExactSizeIterator::len(&iter);
+            assert_eq!(remaining, total - visited.len());
+            match std::iter::Iterator::next(&mut iter) {
+                Some((pos, value)) => {
+                    assert_eq!(*value, pos.to_string());
+                    assert!(super_area.contains(&pos));
+                    visited.push(pos);
+                }
+                None => {
+                    assert_eq!(remaining, 0);
+                    break;
+                }
+            }
+        }
+        let set: HashSet<_> = visited.iter().copied().collect();
+        assert_eq!(set, expected.iter().copied().collect());
+        if set.len() == visited.len() {
+            assert_eq!(total, expected.len());
+        }
+        assert!(super_area.area_len(size) > 0);
+        visited
+    }
+
+    #[test]
+    fn test_union_intersection() {
+        let matrix = matrix();
+        let a = Coord(5, 4) | Coord(1, 1);
+        let b = Coord(0, 0) | Coord(2, 2);
+        let union = check(&matrix, matrix.scanner(a).union(matrix.scanner(b)));
+        assert_eq!(union.len(), 12 + 9 - 4);
+        assert_eq!(
+            union.iter().copied().collect::<HashSet<_>>().len(),
+            union.len()
+        );
+
+        let intersection = check(&matrix, matrix.scanner(a).intersection(matrix.scanner(b)));
+        assert_eq!(
+            intersection,
+            vec![Coord(0, 0), Coord(1, 0), Coord(0, 1), Coord(1, 1)]
+        );
+        let accessor = matrix.scanner(a).intersection(matrix.scanner(b));
+        assert_eq!(accessor.super_area(), Coord(0, 0) | Coord(1, 1));
+
+        let disjoint = check(
+            &matrix,
+            matrix
+                .scanner(Coord(0, 0) | Coord(1, 1))
+                .intersection(matrix.scanner(Coord(3, 3) | Coord(4, 4))),
+        );
+        assert!(disjoint.is_empty());
+    }
+
+    #[test]
+    fn test_duplicated_inputs() {
+        let matrix = matrix();
+        let a = || matrix.scanner(Coord(0, 0) | Coord(2, 1));
+        let b = || matrix.scanner(Coord(2, 1) | Coord(3, 2));
+
+        let union = check(&matrix, a().chain(a()).union(b().chain(b())));
+        assert_eq!(union.len(), 6 + 4 - 1);
+        assert_eq!(
+            union.iter().copied().collect::<HashSet<_>>().len(),
+            union.len()
+        );
+        assert_eq!(matrix.select(a().chain(a()).union(b())).len(), 9);
+
+        let intersection = check(&matrix, a().chain(a()).intersection(b()));
+        assert_eq!(intersection, vec![Coord(2, 1)]);
+    }
+
+    #[test]
+    fn test_filter_step_by_chain() {
+        let matrix = matrix();
+        let area = Coord(4, 3) | Coord(2, 1);
+
+        let filtered = check(&matrix, matrix.scanner(area).filter(|pos| pos.0 == pos.1));
+        assert_eq!(filtered, vec![Coord(4, 4), Coord(0, 0), Coord(1, 1)]);
+
+        let strided = check(&matrix, matrix.scanner(area).step_by(Coord(2, 2)));
+        assert_eq!(
+            strided,
+            vec![
+                Coord(4, 3),
+                Coord(6, 3),
+                Coord(1, 3),
+                Coord(4, 0),
+                Coord(6, 0),
+                Coord(1, 0)
+            ]
+        );
+
+        let chained = check(
+            &matrix,
+            matrix
+                .scanner(Coord(0, 0) | Coord(1, 0))
+                .chain(matrix.scanner(Coord(1, 0) | Coord(1, 1))),
+        );
+        assert_eq!(
+            chained,
+            vec![Coord(0, 0), Coord(1, 0), Coord(1, 0), Coord(1, 1)]
+        );
+
+        let nested = matrix
+            .scanner(area)
+            .union(matrix.scanner(Coord(3, 3) | Coord(3, 3)))
+            .filter(|pos| pos.0 != 5)
+            .step_by(Coord(1, 2));
+        check(&matrix, nested);
+    }
+
+    #[test]
+    fn test_display() {
+        let matrix = matrix();
+        let accessor = matrix
+            .scanner(Coord(6, 4) | Coord(0, 0))
+            .union(matrix.scanner(Coord(1, 1) | Coord(1, 1)));
+        let text = matrix.select(accessor).to_string();
+        let head: Vec<_> = text
+            .lines()
+            .next()
+            .unwrap()
+            .split(',')
+            .map(str::trim)
+            .collect();
+        assert_eq!(head, vec!["MUnion", "6", "0", "1"]);
+        assert_eq!(text.lines().count(), 4);
+        assert!(text.contains("\"(1, 1)\""));
+        assert!(!text.contains("\"(1, 0)\""));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_select_other_size() {
+        let matrix = matrix();
+        let other = M::new(&Coord(3, 3));
+        matrix.select(other.scanner(Coord(0, 0) | Coord(2, 2)));
+    }
+}
@@ -0,0 +1,283 @@
+/// 运行时决定分块大小的[`Matrix`]
+///
+/// 接口和[`Matrix`]相同，但计算偏移量时要用运行时的块大小做除法，
+/// 编译器不能把除法优化成移位，频繁访问的地方仍然应该使用[`Matrix`]。
+pub struct DynMatrix<Element> {
+    elements: Vec<Element>,
+    size: Coord<isize>,
+    /// 块的宽度和高度
+    chunk: Coord<usize>,
+    /// 每一行有多少个块
+    chunk_row_count: usize,
+}
+
+#[allow(dead_code)]
+impl<Element> DynMatrix<Element> {
+    /// 构造大小为`size`、块大小为`chunk`的矩阵，见[`Matrix::with_ctor`]
+    ///
+    /// *不能构造的时候panic，见[`DynMatrix::try_with_ctor`]*
+    pub fn with_ctor(
+        size: &Coord<usize>,
+        chunk: Coord<usize>,
+        constructor: impl FnMut(Option<Coord<isize>>) -> Element,
+    ) -> Self {
+        Self::try_with_ctor(size, chunk, constructor).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// 同[`DynMatrix::with_ctor`]，错误同[`Matrix::try_with_ctor`]，
+    /// 其中[`MatrixError::ZeroChunkSize`]表示`chunk`的任何一个维度是0
+    pub fn try_with_ctor(
+        size: &Coord<usize>,
+        chunk: Coord<usize>,
+        mut constructor: impl FnMut(Option<Coord<isize>>) -> Element,
+    ) -> Result<Self, MatrixError> {
+        let alloc_size = check_chunked_alloc_size(*size, chunk, std::mem::size_of::<Element>())?;
+        let chunk_row_count = size.0.div_ceil(chunk.0);
+        let mut instance = Self {
+            elements: Vec::with_capacity(alloc_size),
+            size: Coord(size.0 as isize, size.1 as isize),
+            chunk,
+            chunk_row_count,
+        };
+        let area = Coord(0, 0) | (instance.size - Coord(1, 1));
+        for i in 0..alloc_size {
+            let pos = instance.pos_at(i);
+            let verified_pos = if area.contains(&pos) { Some(pos) } else { None };
+            instance.elements.push(constructor(verified_pos));
+        }
+        Ok(instance)
+    }
+
+    #[inline]
+    pub const fn size(&self) -> &Coord<isize> {
+        &self.size
+    }
+
+    /// 块的宽度和高度
+    #[inline]
+    pub const fn chunk_size(&self) -> Coord<usize> {
+        self.chunk
+    }
+
+    #[inline]
+    pub fn normalize(&self, pos: Coord<isize>) -> Coord<isize> {
+        Torus::new(self.size).normalize(pos)
+    }
+
+    #[inline]
+    pub fn normalize_area(&self, area: Coord<Interval<isize>>) -> Coord<Interval<isize>> {
+        self.normalize(area.from()) | self.normalize(area.to())
+    }
+
+    /// 按行遍历`area`
+    pub fn area(&self, area: Coord<Interval<isize>>) -> DynIterator<'_, Element> {
+        let area = self.normalize_area(area);
+        DynIterator {
+            matrix: self,
+            at: area.from(),
+            length: measure_area(self.size, area).merge(std::ops::Mul::mul) as usize,
+            area,
+        }
+    }
+
+    #[inline]
+    pub fn iter(&self) -> DynIterator<'_, Element> {
+        self.area(Coord(0, 0) | (self.size - Coord(1, 1)))
+    }
+
+    /// 已经标准化的`pos`对应的偏移量
+    #[inline]
+    fn calc_address(&self, pos: Coord<isize>) -> usize {
+        let (x, y) = (pos.0 as usize, pos.1 as usize);
+        let chunk_address = x / self.chunk.0 + y / self.chunk.1 * self.chunk_row_count;
+        let grid_address = x % self.chunk.0 + y % self.chunk.1 * self.chunk.0;
+        chunk_address * self.chunk.0 * self.chunk.1 + grid_address
+    }
+
+    /// 偏移量`addr`对应的位置，可能不在矩阵之内
+    #[inline]
+    fn pos_at(&self, addr: usize) -> Coord<isize> {
+        let chunk_len = self.chunk.0 * self.chunk.1;
+        let (chunk_address, grid_address) = (addr / chunk_len, addr % chunk_len);
+        Coord(
+            (chunk_address % self.chunk_row_count * self.chunk.0 + grid_address % self.chunk.0)
+                as isize,
+            (chunk_address / self.chunk_row_count * self.chunk.1 + grid_address / self.chunk.0)
+                as isize,
+        )
+    }
+}
+
+#[allow(dead_code)]
+impl<Element: Default> DynMatrix<Element> {
+    #[inline]
+    pub fn new(size: &Coord<usize>, chunk: Coord<usize>) -> Self {
+        Self::with_ctor(size, chunk, |_| Element::default())
+    }
+
+    /// 同[`DynMatrix::new`]，错误见[`DynMatrix::try_with_ctor`]
+    #[inline]
+    pub fn try_new(size: &Coord<usize>, chunk: Coord<usize>) -> Result<Self, MatrixError> {
+        Self::try_with_ctor(size, chunk, |_| Element::default())
+    }
+}
+
+#[allow(dead_code)]
+impl<Element: Clone> DynMatrix<Element> {
+    #[inline]
+    pub fn with_fill(size: &Coord<usize>, chunk: Coord<usize>, element: &Element) -> Self {
+        Self::with_ctor(size, chunk, |_| element.clone())
+    }
+}
+
+impl<Element: Clone> Clone for DynMatrix<Element> {
+    fn clone(&self) -> Self {
+        Self {
+            elements: self.elements.clone(),
+            size: self.size,
+            chunk: self.chunk,
+            chunk_row_count: self.chunk_row_count,
+        }
+    }
+}
+
+impl<Element> std::ops::Index<Coord<isize>> for DynMatrix<Element> {
+    type Output = Element;
+
+    #[inline]
+    fn index(&self, index: Coord<isize>) -> &Element {
+        &self.elements[self.calc_address(self.normalize(index))]
+    }
+}
+
+impl<Element> std::ops::IndexMut<Coord<isize>> for DynMatrix<Element> {
+    #[inline]
+    fn index_mut(&mut self, index: Coord<isize>) -> &mut Element {
+        let addr = self.calc_address(self.normalize(index));
+        &mut self.elements[addr]
+    }
+}
+
+impl<Element: std::string::ToString> std::fmt::Display for DynMatrix<Element> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.iter().fmt_with(f, HEAD_MATRIX)
+    }
+}
+
+/// 按行遍历[`DynMatrix`]中区域的迭代器
+pub struct DynIterator<'m, Element> {
+    matrix: &'m DynMatrix<Element>,
+    at: Coord<isize>,
+    length: usize,
+    area: Coord<Interval<isize>>,
+}
+
+impl<'m, Element> std::iter::Iterator for DynIterator<'m, Element> {
+    type Item = (Coord<isize>, &'m Element);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.length == 0 {
+            return None;
+        }
+        let at = self.at;
+        self.length -= 1;
+        let size = self.matrix.size;
+        self.at = if at.0 == self.area.0.to {
+            Coord(self.area.0.from, wrapping_inc(at.1, size.1))
+        } else {
+            Coord(wrapping_inc(at.0, size.0), at.1)
+        };
+        Some((at, &self.matrix.elements[self.matrix.calc_address(at)]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<'m, Element> std::iter::ExactSizeIterator for DynIterator<'m, Element> {}
+
+impl<'m, Element> std::iter::FusedIterator for DynIterator<'m, Element> {}
+
+impl<'m, Element: std::string::ToString> std::fmt::Display for DynIterator<'m, Element> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, "MScan")
+    }
+}
+
+impl<'m, Element: std::string::ToString> DynIterator<'m, Element> {
+    fn fmt_with(&self, f: &mut std::fmt::Formatter<'_>, head: &str) -> std::fmt::Result {
+        write_sheet(f, head, self.matrix.size, self.area, |pos| {
+            print_element(&self.matrix[pos])
+        })
+    }
+}
+
+#[cfg(test)]
+mod dyn_matrix_tests {
+    use super::*;
+
+    fn ctor(opt_pos: Option<Coord<isize>>) -> String {
+        opt_pos.map_or(String::new(), |pos| pos.to_string())
+    }
+
+    fn test_sub<const CW: usize, const CH: usize>() {
+        let size = Coord(7, 5);
+        let fixed = Matrix::<String, CW, CH>::with_ctor(&size, ctor);
+        let mut dynamic = DynMatrix::with_ctor(&size, Coord(CW, CH), ctor);
+        assert_eq!(dynamic.size(), fixed.size());
+        // 同样的块大小，内存布局也相同
+        assert_eq!(dynamic.elements, fixed.elements);
+
+        for area in [
+            Coord(0, 0) | Coord(6, 4),
+            Coord(5, 3) | Coord(1, 1),
+            Coord(-2, 7) | Coord(3, 2),
+        ] {
+            assert!(dynamic.area(area).eq(fixed.area(area)));
+            assert_eq!(dynamic.area(area).len(), fixed.area(area).len());
+            assert_eq!(dynamic.area(area).to_string(), fixed.area(area).to_string());
+        }
+        assert_eq!(dynamic.to_string(), fixed.to_string());
+
+        dynamic[Coord(-1, -1)] = "last".to_string();
+        assert_eq!(dynamic[Coord(6, 4)], "last");
+        assert_eq!(dynamic.clone()[Coord(13, 9)], "last");
+    }
+
+    #[test]
+    fn test_same_as_matrix() {
+        test_sub::<1, 1>();
+        test_sub::<2, 3>();
+        test_sub::<4, 4>();
+        test_sub::<8, 8>();
+    }
+
+    #[test]
+    fn test_invalid_size() {
+        assert!(DynMatrix::<u8>::try_new(&Coord(3, 4), Coord(2, 3)).is_ok());
+        assert_eq!(
+            DynMatrix::<u8>::try_new(&Coord(0, 4), Coord(2, 3)).err(),
+            Some(MatrixError::ZeroSize {
+                size: Coord(0, 4).into()
+            })
+        );
+        assert_eq!(
+            DynMatrix::<u8>::try_new(&Coord(3, 4), Coord(2, 0)).err(),
+            Some(MatrixError::ZeroChunkSize {
+                chunk: Coord(2, 0).into()
+            })
+        );
+        let wide = Coord(1 << 31, 1 << 31);
+        assert_eq!(
+            DynMatrix::<u64>::try_new(&wide, Coord(1, 1)).err(),
+            Some(MatrixError::AllocationOverflow { size: wide.into() })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must not be zero")]
+    fn test_zero_chunk_panics() {
+        DynMatrix::<u8>::new(&Coord(3, 4), Coord(0, 1));
+    }
+}
@@ -0,0 +1,99 @@
+/// 矩阵操作失败的原因
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MatrixError {
+    /// 两个矩阵（或矩阵和补丁）的大小不同
+    SizeMismatch {
+        expected: Coord<isize>,
+        found: Coord<isize>,
+    },
+    /// 要求互不相交的两个区域相交
+    AreaOverlap {
+        a: Coord<Interval<isize>>,
+        b: Coord<Interval<isize>>,
+    },
+    /// 矩阵的某个维度是0
+    ZeroSize { size: Dims },
+    /// 块的某个维度是0
+    ZeroChunkSize { chunk: Dims },
+    /// 矩阵的某个维度超过[`isize::MAX`]
+    SizeOverflow { size: Dims },
+    /// 需要分配的空间超过[`isize::MAX`]字节
+    AllocationOverflow { size: Dims },
+}
+
+impl std::fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatrixError::SizeMismatch { expected, found } => {
+                write!(f, "size mismatch: expected {}, found {}", expected, found)
+            }
+            MatrixError::AreaOverlap { a, b } => {
+                write!(f, "areas {} and {} overlap", a, b)
+            }
+            MatrixError::ZeroSize { size } => {
+                write!(f, "matrix size must not be zero, found {}", size)
+            }
+            MatrixError::ZeroChunkSize { chunk } => {
+                write!(f, "chunk size must not be zero, found {}", chunk)
+            }
+            MatrixError::SizeOverflow { size } => {
+                write!(f, "matrix size {} exceeds isize::MAX", size)
+            }
+            MatrixError::AllocationOverflow { size } => {
+                write!(f, "matrix of size {} is too large to allocate", size)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+/// 二维或三维矩阵（或块）的大小，用在[`MatrixError`]中
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Dims {
+    Two(Coord<usize>),
+    Three(Coord3<usize>),
+}
+
+impl Dims {
+    /// 每个维度的长度
+    fn lens(self) -> Vec<usize> {
+        match self {
+            Dims::Two(size) => vec![size.0, size.1],
+            Dims::Three(size) => vec![size.0, size.1, size.2],
+        }
+    }
+}
+
+impl From<Coord<usize>> for Dims {
+    #[inline]
+    fn from(size: Coord<usize>) -> Self {
+        Dims::Two(size)
+    }
+}
+
+impl From<Coord3<usize>> for Dims {
+    #[inline]
+    fn from(size: Coord3<usize>) -> Self {
+        Dims::Three(size)
+    }
+}
+
+impl std::fmt::Display for Dims {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dims::Two(size) => write!(f, "{}", size),
+            Dims::Three(size) => write!(f, "{}", size),
+        }
+    }
+}
+
+/// 大小不同时返回[`MatrixError::SizeMismatch`]
+#[inline]
+fn check_size(expected: Coord<isize>, found: Coord<isize>) -> Result<(), MatrixError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(MatrixError::SizeMismatch { expected, found })
+    }
+}
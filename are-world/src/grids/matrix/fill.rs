@@ -0,0 +1,289 @@
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 把`area`中的格子都设为`value`，`area`会先标准化。
+    ///
+    /// 整块宽度的部分一次填充连续的内存
+    pub fn fill_area(&mut self, area: Coord<Interval<isize>>, value: &Element)
+    where
+        Element: Clone,
+    {
+        for piece in self.chunk_pieces(area) {
+            let addr = unsafe { Self::calc_address_unchecked(self.size, piece.from()) };
+            let Coord(width, height) = piece.map(|i| (i.to - i.from + 1) as usize);
+            if width == CHUNK_WIDTH {
+                self.elements[addr..addr + width * height].fill(value.clone());
+            } else {
+                for row in 0..height {
+                    let start = addr + row * CHUNK_WIDTH;
+                    self.elements[start..start + width].fill(value.clone());
+                }
+            }
+        }
+    }
+
+    /// 把`area`中位置为`pos`的格子设为`constructor(pos)`，按存储顺序调用，`area`会先标准化
+    pub fn fill_area_with(
+        &mut self,
+        area: Coord<Interval<isize>>,
+        mut constructor: impl FnMut(Coord<isize>) -> Element,
+    ) {
+        self.for_each_row_mut(area, |start, row| {
+            for (pos, element) in (start.0..).map(|x| Coord(x, start.1)).zip(row) {
+                *element = constructor(pos);
+            }
+        });
+    }
+
+    /// 把`area`中`predicate(pos, 元素)`为`true`的格子设为`value`，返回替换了多少个格子。
+    /// `area`会先标准化
+    pub fn replace_where(
+        &mut self,
+        area: Coord<Interval<isize>>,
+        mut predicate: impl FnMut(Coord<isize>, &Element) -> bool,
+        value: &Element,
+    ) -> usize
+    where
+        Element: Clone,
+    {
+        let mut count = 0;
+        self.for_each_row_mut(area, |start, row| {
+            for (pos, element) in (start.0..).map(|x| Coord(x, start.1)).zip(row) {
+                if predicate(pos, element) {
+                    *element = value.clone();
+                    count += 1;
+                }
+            }
+        });
+        count
+    }
+
+    /// 交换`a`和`b`两个区域的内容，`a.from()`和`b.from()`对应，区域可以跨过边界。
+    ///
+    /// 两个区域大小不同时返回[`MatrixError::SizeMismatch`]，
+    /// 两个区域相交时返回[`MatrixError::AreaOverlap`]，矩阵不变
+    pub fn swap_areas(
+        &mut self,
+        a: Coord<Interval<isize>>,
+        b: Coord<Interval<isize>>,
+    ) -> Result<(), MatrixError> {
+        let a = self.normalize_area(a);
+        let b = self.normalize_area(b);
+        let size = self.size;
+        let a_size = size.reduce(a, |s, i| i.len(s));
+        let b_size = size.reduce(b, |s, i| i.len(s));
+        check_size(a_size, b_size)?;
+        if !a.intersect(b, size).is_empty() {
+            return Err(MatrixError::AreaOverlap { a, b });
+        }
+        for piece in self.chunk_pieces(a) {
+            for y in piece.1.from..=piece.1.to {
+                // `a`的一段行在`b`中对应的格子可能跨过`b`的块的边界或者矩阵的边界，再切开
+                let mut pos_a = Coord(piece.0.from, y);
+                while pos_a.0 <= piece.0.to {
+                    let pos_b = self.normalize(b.from() + (pos_a - a.from()));
+                    let len = (piece.0.to - pos_a.0 + 1)
+                        .min(CHUNK_WIDTH as isize - pos_b.0 % CHUNK_WIDTH as isize)
+                        .min(size.0 - pos_b.0) as usize;
+                    // SAFETY: `pos_a`和`pos_b`都是标准化的位置
+                    let addr_a = unsafe { Self::calc_address_unchecked(size, pos_a) };
+                    let addr_b = unsafe { Self::calc_address_unchecked(size, pos_b) };
+                    // 两个区域不相交，所以两段不重叠
+                    let (low, high) = (addr_a.min(addr_b), addr_a.max(addr_b));
+                    let (left, right) = self.elements.split_at_mut(high);
+                    left[low..low + len].swap_with_slice(&mut right[..len]);
+                    pos_a.0 += len as isize;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// private
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 把标准化以后的`area`在边界处和块的边界处切开，每个区域都在一个块里
+    fn chunk_pieces(&self, area: Coord<Interval<isize>>) -> Vec<Coord<Interval<isize>>> {
+        let split = |interval: Interval<isize>, chunk: isize| {
+            let mut pieces = vec![];
+            let mut from = interval.from;
+            while from <= interval.to {
+                let to = interval.to.min((from / chunk + 1) * chunk - 1);
+                pieces.push(Interval::new(from, to));
+                from = to + 1;
+            }
+            pieces
+        };
+        let mut pieces = vec![];
+        for piece in self.normalize_area(area).split_at_seams(self.size) {
+            for y in split(piece.1, CHUNK_HEIGHT as isize) {
+                for x in split(piece.0, CHUNK_WIDTH as isize) {
+                    pieces.push(Coord(x, y));
+                }
+            }
+        }
+        pieces
+    }
+
+    /// 对`area`在每个块里的每一行调用`f(这一行第一个格子的位置, 这一行的元素)`
+    fn for_each_row_mut(
+        &mut self,
+        area: Coord<Interval<isize>>,
+        mut f: impl FnMut(Coord<isize>, &mut [Element]),
+    ) {
+        for piece in self.chunk_pieces(area) {
+            let addr = unsafe { Self::calc_address_unchecked(self.size, piece.from()) };
+            let width = (piece.0.to - piece.0.from + 1) as usize;
+            for (row, y) in (piece.1.from..=piece.1.to).enumerate() {
+                let start = addr + row * CHUNK_WIDTH;
+                f(
+                    Coord(piece.0.from, y),
+                    &mut self.elements[start..start + width],
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod fill_tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn source<const CW: usize, const CH: usize>() -> Matrix<i32, CW, CH> {
+        Matrix::with_ctor(&Coord(7, 5), |opt_pos| {
+            opt_pos.map_or(-1, |pos| (pos.0 + 10 * pos.1) as i32)
+        })
+    }
+
+    fn areas() -> [Coord<Interval<isize>>; 5] {
+        [
+            Coord(1, 1) | Coord(5, 3),
+            Coord(5, 3) | Coord(1, 1),
+            Coord(0, 0) | Coord(6, 4),
+            Coord(-2, 4) | Coord(8, 5),
+            Coord(3, 2) | Coord(3, 2),
+        ]
+    }
+
+    fn check<const CW: usize, const CH: usize>() {
+        for area in areas() {
+            let inside = source::<CW, CH>()
+                .area(area)
+                .map(|(pos, _)| pos)
+                .collect::<HashSet<_>>();
+            let value_at = |pos: Coord<isize>| (pos.0 + 10 * pos.1) as i32;
+
+            let mut filled = source::<CW, CH>();
+            filled.fill_area(area, &-1);
+            for (pos, value) in filled.iter() {
+                let expected = if inside.contains(&pos) {
+                    -1
+                } else {
+                    value_at(pos)
+                };
+                assert_eq!(*value, expected);
+            }
+
+            let mut filled = source::<CW, CH>();
+            filled.fill_area_with(area, |pos| -value_at(pos));
+            for (pos, value) in filled.iter() {
+                let expected = if inside.contains(&pos) {
+                    -value_at(pos)
+                } else {
+                    value_at(pos)
+                };
+                assert_eq!(*value, expected);
+            }
+
+            let mut replaced = source::<CW, CH>();
+            let even = |pos: Coord<isize>| pos.0 % 2 == 0;
+            let count = replaced.replace_where(area, |pos, _| even(pos), &-1);
+            assert_eq!(count, inside.iter().filter(|pos| even(**pos)).count());
+            for (pos, value) in replaced.iter() {
+                let replace = inside.contains(&pos) && even(pos);
+                assert_eq!(*value, if replace { -1 } else { value_at(pos) });
+            }
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        check::<1, 1>();
+        check::<2, 3>();
+        check::<4, 4>();
+        check::<8, 8>();
+    }
+
+    #[test]
+    fn test_swap_areas() {
+        let mut matrix = source::<2, 3>();
+        let a = Coord(5, 4) | Coord(6, 5);
+        let b = Coord(1, 1) | Coord(2, 2);
+        matrix.swap_areas(a, b).unwrap();
+        assert_eq!(matrix[Coord(5, 4)], 11);
+        assert_eq!(matrix[Coord(6, 0)], 22);
+        assert_eq!(matrix[Coord(2, 2)], 6);
+        assert_eq!(matrix[Coord(1, 1)], 45);
+        assert_eq!(matrix[Coord(3, 3)], 33);
+        matrix.swap_areas(b, a).unwrap();
+        assert_eq!(
+            matrix.iter().collect::<Vec<_>>(),
+            source::<2, 3>().iter().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            matrix.swap_areas(a, Coord(1, 1) | Coord(3, 2)),
+            Err(MatrixError::SizeMismatch {
+                expected: Coord(2, 2),
+                found: Coord(3, 2)
+            })
+        );
+        let overlapping = Coord(6, 0) | Coord(0, 1);
+        assert_eq!(
+            matrix.swap_areas(a, overlapping),
+            Err(MatrixError::AreaOverlap {
+                a: Coord(5, 4) | Coord(6, 0),
+                b: overlapping
+            })
+        );
+    }
+
+    fn check_swap<const CW: usize, const CH: usize>() {
+        // 两个区域在不同的地方跨过块和矩阵的边界
+        let pairs = [
+            (Coord(0, 0) | Coord(2, 1), Coord(3, 2) | Coord(5, 3)),
+            (Coord(5, 4) | Coord(1, 0), Coord(2, 1) | Coord(5, 2)),
+            (Coord(1, 0) | Coord(6, 1), Coord(-2, 3) | Coord(3, 4)),
+            (Coord(4, 1) | Coord(4, 3), Coord(0, 2) | Coord(0, 4)),
+        ];
+        for (a, b) in pairs {
+            let mut matrix = source::<CW, CH>();
+            matrix.swap_areas(a, b).unwrap();
+            let size = *matrix.size();
+            let a_size = size.reduce(a, |s, i| i.len(s));
+            let mut expected = source::<CW, CH>();
+            for dy in 0..a_size.1 {
+                for dx in 0..a_size.0 {
+                    let (pos_a, pos_b) = (a.from() + Coord(dx, dy), b.from() + Coord(dx, dy));
+                    let (value_a, value_b) = (expected[pos_a], expected[pos_b]);
+                    expected[pos_a] = value_b;
+                    expected[pos_b] = value_a;
+                }
+            }
+            assert!(matrix.iter().eq(expected.iter()), "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_swap_unaligned() {
+        check_swap::<1, 1>();
+        check_swap::<2, 3>();
+        check_swap::<3, 2>();
+        check_swap::<4, 4>();
+        check_swap::<8, 8>();
+    }
+}
@@ -36,64 +36,83 @@ where
     Element: std::string::ToString,
 {
     fn fmt_with(&self, f: &mut std::fmt::Formatter<'_>, head: &str) -> std::fmt::Result {
-        let start = self.accessor.super_area().from();
-        let size = measure_area(*self.matrix.size(), self.accessor.super_area());
-        let sheet_size = Coord(size.0 as usize + 1, size.1 as usize + 1);
-
-        let constructor = |opt_index: Option<Coord<isize>>| {
-            if let Some(display_offset) = opt_index {
-                if display_offset.0 == 0 {
-                    if display_offset.1 == 0 {
-                        head.to_string()
-                    } else {
-                        let Coord(_, display_index) =
-                            self.matrix.normalize(start + display_offset - Coord(1, 1));
-                        print_index(display_index)
-                    }
-                } else if display_offset.1 == 0 {
-                    let Coord(display_index, _) =
-                        self.matrix.normalize(start + display_offset - Coord(1, 1));
-                    print_index(display_index)
+        write_sheet(
+            f,
+            head,
+            *self.matrix.size(),
+            self.accessor.super_area(),
+            |pos| {
+                if self.accessor.contains(pos) {
+                    print_element(&self.matrix[pos])
                 } else {
-                    let index = display_offset - Coord(1, 1);
-                    let pos = start + index;
-                    if self.accessor.contains(pos) {
-                        print_element(&self.matrix[pos])
-                    } else {
-                        NONE.into()
-                    }
+                    NONE.into()
                 }
+            },
+        )
+    }
+}
+
+/// 把`matrix_size`大小的矩阵中的`area`画成表格，第一行和第一列是下标，
+/// 表格中`pos`处的内容是`cell(pos)`，`pos`已经标准化
+fn write_sheet(
+    f: &mut std::fmt::Formatter<'_>,
+    head: &str,
+    matrix_size: Coord<isize>,
+    area: Coord<Interval<isize>>,
+    cell: impl Fn(Coord<isize>) -> String,
+) -> std::fmt::Result {
+    let torus = Torus::new(matrix_size);
+    let start = area.from();
+    let size = measure_area(matrix_size, area);
+    let sheet_size = Coord(size.0 as usize + 1, size.1 as usize + 1);
+
+    let constructor = |opt_index: Option<Coord<isize>>| {
+        if let Some(display_offset) = opt_index {
+            if display_offset.0 == 0 {
+                if display_offset.1 == 0 {
+                    head.to_string()
+                } else {
+                    let Coord(_, display_index) =
+                        torus.normalize(start + display_offset - Coord(1, 1));
+                    print_index(display_index)
+                }
+            } else if display_offset.1 == 0 {
+                let Coord(display_index, _) = torus.normalize(start + display_offset - Coord(1, 1));
+                print_index(display_index)
             } else {
-                String::new()
+                let index = display_offset - Coord(1, 1);
+                cell(torus.normalize(start + index))
             }
-        };
-
-        let sheet = Matrix::<String, 1, 1>::with_ctor(&sheet_size, constructor);
-        let mut widths = vec![0usize; sheet_size.0];
-        for (p, s) in sheet.iter() {
-            let col = p.0 as usize;
-            let len = string_width(s);
-            widths[col] = std::cmp::max(widths[col], len);
+        } else {
+            String::new()
         }
+    };
+
+    let sheet = Matrix::<String, 1, 1>::with_ctor(&sheet_size, constructor);
+    let mut widths = vec![0usize; sheet_size.0];
+    for (p, s) in sheet.iter() {
+        let col = p.0 as usize;
+        let len = string_width(s);
+        widths[col] = std::cmp::max(widths[col], len);
+    }
 
-        for j in 0..size.1 + 1 {
-            for i in 0..size.0 + 1 {
-                if i != 0 {
-                    write!(f, "{}", DELIM)?;
-                } else if j != 0 {
-                    write!(f, "{}", LINE_DELIM)?;
-                }
-                write!(
-                    f,
-                    "{value:^width$}",
-                    value = sheet[Coord(i, j)],
-                    width = widths[i as usize],
-                )?;
+    for j in 0..size.1 + 1 {
+        for i in 0..size.0 + 1 {
+            if i != 0 {
+                write!(f, "{}", DELIM)?;
+            } else if j != 0 {
+                write!(f, "{}", LINE_DELIM)?;
             }
+            write!(
+                f,
+                "{value:^width$}",
+                value = sheet[Coord(i, j)],
+                width = widths[i as usize],
+            )?;
         }
-
-        Ok(())
     }
+
+    Ok(())
 }
 
 #[inline]
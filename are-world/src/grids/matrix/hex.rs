@@ -0,0 +1,403 @@
+/// 把矩阵看作odd-r六边形网格，从中心一圈一圈向外访问的访问器，见[`Matrix::hex_range`]
+///
+/// 每个格子只访问一次，所在的圈数等于它在环面上到中心的步数，见[`Torus::hex`]。
+/// 同一圈内从左上角开始顺时针访问。
+#[derive(Clone)]
+pub struct HexRings<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    center: Coord<isize>,
+    min_radius: isize,
+    max_radius: isize,
+
+    radius: isize,
+    index: isize,
+    length: usize,
+
+    torus: Torus,
+}
+
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> HexRings<CHUNK_WIDTH, CHUNK_HEIGHT> {
+    /// `center`必须已经标准化，`matrix_size.1`必须是偶数
+    fn new(
+        matrix_size: Coord<isize>,
+        center: Coord<isize>,
+        min_radius: usize,
+        max_radius: usize,
+    ) -> Self {
+        let torus = Torus::new(matrix_size);
+        // 环面上任意两个格子之间的步数都不超过宽度加高度
+        let limit = matrix_size.0 + matrix_size.1;
+        let min_radius = std::cmp::min(min_radius.try_into().unwrap_or(isize::MAX), limit + 1);
+        let max_radius = std::cmp::min(max_radius.try_into().unwrap_or(isize::MAX), limit);
+
+        // 和中心的步数只和位移有关，所以数偏移量或者数格子都可以，取少的一边
+        let in_range = |steps: isize| min_radius <= steps && steps <= max_radius;
+        let offset_count = (min_radius..=max_radius)
+            .map(|r| if r == 0 { 1 } else { 6 * r })
+            .sum::<isize>();
+        let length = if offset_count <= matrix_size.0 * matrix_size.1 {
+            (min_radius..=max_radius)
+                .flat_map(|r| (0..std::cmp::max(6 * r, 1)).map(move |i| (r, i)))
+                .filter_map(|(r, i)| Self::ring_offset(r, i))
+                .filter(|offset| Self::is_canonical(torus, center, *offset))
+                .count()
+        } else {
+            (0..matrix_size.1)
+                .flat_map(|y| (0..matrix_size.0).map(move |x| Coord(x, y)))
+                .filter(|pos| in_range(torus.hex(center, *pos)))
+                .count()
+        };
+
+        Self {
+            center,
+            min_radius,
+            max_radius,
+            radius: min_radius,
+            index: 0,
+            length,
+            torus,
+        }
+    }
+
+    /// 第`radius`圈的第`index`个偏移量，圈上没有这么多格子时返回[`None`]
+    fn ring_offset(radius: isize, index: isize) -> Option<Axial> {
+        if radius == 0 {
+            return if index == 0 { Some(Axial(0, 0)) } else { None };
+        }
+        const CORNER: Axial = Axial::DIRECTIONS[2];
+        const STEPS: [usize; 6] = [0, 5, 4, 3, 2, 1];
+        let side = (index / radius) as usize;
+        let step = Axial::DIRECTIONS[*STEPS.get(side)?];
+        let mut offset = CORNER * radius;
+        for s in STEPS.iter().take(side) {
+            offset = offset + Axial::DIRECTIONS[*s] * radius;
+        }
+        Some(offset + step * (index % radius))
+    }
+
+    /// `offset`是否是[`Torus::hex_displacement`]会给出的位移，
+    /// 同一个格子只有一个这样的偏移量
+    #[inline]
+    fn is_canonical(torus: Torus, center: Coord<isize>, offset: Axial) -> bool {
+        let pos = (Axial::from_offset(center) + offset).to_offset();
+        torus.hex_displacement(center, pos) == offset
+    }
+}
+
+// SAFETY: 位置都经过标准化
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>
+    for HexRings<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        while self.length > 0 && self.radius <= self.max_radius {
+            let offset = match Self::ring_offset(self.radius, self.index) {
+                Some(offset) => offset,
+                None => {
+                    self.radius += 1;
+                    self.index = 0;
+                    continue;
+                }
+            };
+            self.index += 1;
+            if Self::is_canonical(self.torus, self.center, offset) {
+                self.length -= 1;
+                let pos = self
+                    .torus
+                    .normalize((Axial::from_offset(self.center) + offset).to_offset());
+                let addr =
+                    Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address(self.torus.size(), pos);
+                return Some((pos, addr));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        // 离中心不超过`max_radius`步的格子，行列的偏移都不超过`max_radius`
+        let size = self.torus.size();
+        let reach = size.map(|s| {
+            if 2 * self.max_radius + 1 >= s {
+                Interval::new(-(s - 1) / 2, s / 2)
+            } else {
+                Interval::new(-self.max_radius, self.max_radius)
+            }
+        });
+        let from = self
+            .torus
+            .normalize(self.center + Coord(reach.0.from, reach.1.from));
+        let to = self
+            .torus
+            .normalize(self.center + Coord(reach.0.to, reach.1.to));
+        from | to
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        let steps = self.torus.hex(self.center, pos);
+        self.min_radius <= steps && steps <= self.max_radius
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MHexRings"
+    }
+
+    #[inline]
+    fn matrix_size(&self) -> Coord<isize> {
+        self.torus.size()
+    }
+}
+
+/// 按六边形网格错开排版的矩阵，见[`Matrix::hex_display`]
+pub struct HexDisplay<'m, Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    matrix: &'m Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::fmt::Display
+    for HexDisplay<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Element: std::string::ToString,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Coord(width, height) = *self.matrix.size();
+        let cells: Vec<Vec<String>> = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| print_element(&self.matrix[Coord(x, y)]))
+                    .collect()
+            })
+            .collect();
+        let cell_width = cells
+            .iter()
+            .flatten()
+            .map(|s| string_width(s))
+            .max()
+            .unwrap_or(0);
+        // 奇数行右移半格，格子之间隔一个空格，所以半格是`cell_width + 1`的一半
+        let indent = " ".repeat(cell_width.div_ceil(2));
+        for (y, row) in cells.iter().enumerate() {
+            if y != 0 {
+                write!(f, "{}", LINE_DELIM)?;
+            }
+            let mut line = String::new();
+            if y % 2 == 1 {
+                line.push_str(&indent);
+            }
+            for (x, cell) in row.iter().enumerate() {
+                if x != 0 {
+                    line.push(' ');
+                }
+                line.push_str(&format!(
+                    "{value:^width$}",
+                    value = cell,
+                    width = cell_width
+                ));
+            }
+            // 含空格的元素会加引号，所以行尾的空格都是对齐用的
+            write!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// 以下方法把矩阵看作odd-r六边形网格，见[`Axial`]。
+///
+/// *矩阵的高度是奇数时panic*
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 和`pos`相邻的六个格子，顺序同[`Axial::DIRECTIONS`]，见[`Torus::hex_neighbors`]
+    pub fn hex_neighbors(
+        &self,
+        pos: Coord<isize>,
+    ) -> impl std::iter::Iterator<Item = (Coord<isize>, &Element)> {
+        self.assert_hex();
+        self.torus()
+            .hex_neighbors(pos)
+            .into_iter()
+            .map(move |pos| (pos, &self[pos]))
+    }
+
+    /// 和`center`恰好相距`radius`步的格子，从左上角开始顺时针访问
+    #[inline]
+    pub fn hex_ring(
+        &self,
+        center: Coord<isize>,
+        radius: usize,
+    ) -> Iterator<'_, Element, HexRings<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.select(self.hex_rings_scanner(center, radius, radius))
+    }
+
+    /// 和`center`相距不超过`radius`步的格子，从`center`开始一圈一圈向外访问，见[`HexRings`]
+    #[inline]
+    pub fn hex_range(
+        &self,
+        center: Coord<isize>,
+        radius: usize,
+    ) -> Iterator<'_, Element, HexRings<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.select(self.hex_rings_scanner(center, 0, radius))
+    }
+
+    /// 返回访问和`center`相距`min_radius`到`max_radius`步的格子的访问器
+    #[inline]
+    pub fn hex_rings_scanner(
+        &self,
+        center: Coord<isize>,
+        min_radius: usize,
+        max_radius: usize,
+    ) -> HexRings<CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.assert_hex();
+        HexRings::new(self.size, self.normalize(center), min_radius, max_radius)
+    }
+
+    /// 从`a`到`b`的直线经过的格子，见[`Torus::hex_line`]
+    pub fn hex_line(
+        &self,
+        a: Coord<isize>,
+        b: Coord<isize>,
+    ) -> impl std::iter::Iterator<Item = (Coord<isize>, &Element)> {
+        self.assert_hex();
+        self.torus()
+            .hex_line(a, b)
+            .into_iter()
+            .map(move |pos| (pos, &self[pos]))
+    }
+
+    /// 奇数行向右错开半格的排版
+    #[inline]
+    pub fn hex_display(&self) -> HexDisplay<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        HexDisplay { matrix: self }
+    }
+
+    #[inline]
+    fn assert_hex(&self) {
+        assert!(self.size.1 % 2 == 0, "hex layout requires an even height");
+    }
+}
+
+#[cfg(test)]
+mod hex_tests {
+    use super::*;
+
+    fn numbered<const CW: usize, const CH: usize>(size: Coord<usize>) -> Matrix<isize, CW, CH> {
+        Matrix::with_ctor(&size, |opt_pos| {
+            opt_pos.map_or(-1, |pos| pos.0 + 10 * pos.1)
+        })
+    }
+
+    fn check<const CW: usize, const CH: usize>(size: Coord<usize>) {
+        let matrix = numbered::<CW, CH>(size);
+        let torus = matrix.torus();
+        for center in matrix.iter().map(|(pos, _)| pos) {
+            for radius in [0, 1, 2, 3, 7] {
+                let range = matrix.hex_range(center, radius);
+                let len = range.len();
+                let cells: Vec<_> = range.map(|(pos, _)| pos).collect();
+                assert_eq!(cells.len(), len);
+                let expected = matrix
+                    .iter()
+                    .filter(|(pos, _)| torus.hex(center, *pos) <= radius as isize)
+                    .count();
+                assert_eq!(
+                    cells.len(),
+                    expected,
+                    "{} around {} in {}",
+                    radius,
+                    center,
+                    size
+                );
+                let steps: Vec<_> = cells.iter().map(|pos| torus.hex(center, *pos)).collect();
+                assert!(steps.windows(2).all(|w| w[0] <= w[1]));
+
+                let ring: Vec<_> = matrix
+                    .hex_ring(center, radius)
+                    .map(|(pos, _)| pos)
+                    .collect();
+                let expected: Vec<_> = cells
+                    .iter()
+                    .copied()
+                    .filter(|pos| torus.hex(center, *pos) == radius as isize)
+                    .collect();
+                assert_eq!(ring, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex_range() {
+        check::<1, 1>(Coord(1, 2));
+        check::<2, 2>(Coord(3, 4));
+        check::<2, 3>(Coord(7, 6));
+        check::<4, 4>(Coord(12, 10));
+    }
+
+    #[test]
+    fn test_ring_order() {
+        let matrix = numbered::<2, 2>(Coord(9, 8));
+        let ring: Vec<_> = matrix
+            .hex_ring(Coord(4, 4), 1)
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(
+            ring,
+            vec![
+                Coord(3, 3),
+                Coord(4, 3),
+                Coord(5, 4),
+                Coord(4, 5),
+                Coord(3, 5),
+                Coord(3, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hex_neighbors() {
+        let matrix = numbered::<2, 2>(Coord(9, 8));
+        let neighbors: Vec<_> = matrix
+            .hex_neighbors(Coord(0, 5))
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(
+            neighbors,
+            vec![
+                Coord(1, 5),
+                Coord(1, 4),
+                Coord(0, 4),
+                Coord(8, 5),
+                Coord(0, 6),
+                Coord(1, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hex_line() {
+        let matrix = numbered::<2, 2>(Coord(9, 8));
+        let line: Vec<_> = matrix
+            .hex_line(Coord(7, 0), Coord(1, 0))
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(line, vec![7, 8, 0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "hex layout requires an even height")]
+    fn test_odd_height() {
+        numbered::<2, 2>(Coord(4, 3)).hex_range(Coord(0, 0), 1);
+    }
+
+    #[test]
+    fn test_hex_display() {
+        let matrix = numbered::<2, 2>(Coord(3, 2));
+        assert_eq!(matrix.hex_display().to_string(), "0  1  2\n 10 11 12");
+        let matrix = numbered::<2, 2>(Coord(2, 4));
+        assert_eq!(
+            matrix.hex_display().to_string(),
+            "0  1\n 10 11\n20 21\n 30 31"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 // by *StarvinCulex @2021/11/14*
 
+#[allow(dead_code)]
 impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
     Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
 {
@@ -8,23 +9,187 @@ impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
         &self,
         area: Coord<Interval<isize>>,
     ) -> Iterator<Element, Scan<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT> {
-        Iterator::new(self, Scan::new(self.size, self.normalize_area(area)))
+        self.select(self.scanner(area))
     }
 
+    /// 返回按行遍历`area`的访问器，可以组合后交给[`Matrix::select`]
     #[inline]
-    pub fn area(
+    pub fn scanner(&self, area: Coord<Interval<isize>>) -> Scan<CHUNK_WIDTH, CHUNK_HEIGHT> {
+        Scan::new(self.size, self.normalize_area(area))
+    }
+
+    /// 用`accessor`遍历矩阵
+    ///
+    /// *`accessor`不是为这个大小的矩阵创建的时候panic*
+    #[inline]
+    pub fn select<Access: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>>(
+        &self,
+        accessor: Access,
+    ) -> Iterator<'_, Element, Access, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        assert_eq!(
+            accessor.matrix_size(),
+            self.size,
+            "accessor was created for a matrix of another size"
+        );
+        Iterator::new(self, accessor)
+    }
+
+    /// 返回访问`shape`给出的格子的访问器，可以组合后交给[`Matrix::select`]
+    #[inline]
+    pub fn checked<S: Shape>(&self, shape: S) -> Checked<S> {
+        Checked::new(self.size, shape)
+    }
+
+    /// 用`shape`遍历矩阵，`shape`给出的位置会先标准化
+    #[inline]
+    pub fn select_shape<S: Shape>(
+        &self,
+        shape: S,
+    ) -> Iterator<'_, Element, Checked<S>, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.select(self.checked(shape))
+    }
+
+    /// 按列遍历`area`，每一列从上到下
+    #[inline]
+    pub fn column_scan(
+        &self,
+        area: Coord<Interval<isize>>,
+    ) -> Iterator<'_, Element, ColumnScan<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT>
+    {
+        self.select(self.column_scanner(area))
+    }
+
+    /// 返回按列遍历`area`的访问器，见[`Matrix::column_scan`]
+    #[inline]
+    pub fn column_scanner(
+        &self,
+        area: Coord<Interval<isize>>,
+    ) -> ColumnScan<CHUNK_WIDTH, CHUNK_HEIGHT> {
+        ColumnScan::new(self.size, self.normalize_area(area))
+    }
+
+    /// 蛇形遍历`area`：第一行从左到右，第二行从右到左，依此交替
+    #[inline]
+    pub fn serpentine(
         &self,
         area: Coord<Interval<isize>>,
-    ) -> Iterator<Element, impl Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT>
+    ) -> Iterator<'_, Element, Serpentine<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT>
     {
+        self.select(self.serpentine_scanner(area))
+    }
+
+    /// 返回蛇形遍历`area`的访问器，见[`Matrix::serpentine`]
+    #[inline]
+    pub fn serpentine_scanner(
+        &self,
+        area: Coord<Interval<isize>>,
+    ) -> Serpentine<CHUNK_WIDTH, CHUNK_HEIGHT> {
+        Serpentine::new(self.size, self.normalize_area(area))
+    }
+
+    /// 从`center`开始一圈一圈向外访问，只访问到环面上离`center`不超过`max_radius`步的格子。
+    /// `neighborhood`决定步数是曼哈顿距离还是切比雪夫距离，见[`Rings`]
+    #[inline]
+    pub fn rings(
+        &self,
+        center: Coord<isize>,
+        max_radius: usize,
+        neighborhood: Neighborhood,
+    ) -> Iterator<'_, Element, Rings<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.select(self.rings_scanner(center, max_radius, neighborhood))
+    }
+
+    /// 返回一圈一圈向外访问的访问器，见[`Matrix::rings`]
+    #[inline]
+    pub fn rings_scanner(
+        &self,
+        center: Coord<isize>,
+        max_radius: usize,
+        neighborhood: Neighborhood,
+    ) -> Rings<CHUNK_WIDTH, CHUNK_HEIGHT> {
+        Rings::new(self.size, self.normalize(center), max_radius, neighborhood)
+    }
+
+    /// 从`center`开始一圈一圈向外访问整个矩阵
+    #[inline]
+    pub fn spiral(
+        &self,
+        center: Coord<isize>,
+        neighborhood: Neighborhood,
+    ) -> Iterator<'_, Element, Rings<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.rings(center, usize::MAX, neighborhood)
+    }
+
+    /// 找出离`center`最近的、`predicate(pos, element)`为`true`的格子，找到后就停止访问。
+    ///
+    /// 距离相同时取[`Rings`]中先访问的
+    #[inline]
+    pub fn find_nearest(
+        &self,
+        center: Coord<isize>,
+        neighborhood: Neighborhood,
+        mut predicate: impl FnMut(Coord<isize>, &Element) -> bool,
+    ) -> Option<(Coord<isize>, &Element)> {
+        self.spiral(center, neighborhood)
+            .find(|(pos, element)| predicate(*pos, element))
+    }
+
+    /// 以随机顺序访问`area`中的每个格子，顺序只取决于`rng`和`area`。
+    /// `rng`会前进
+    #[inline]
+    pub fn shuffled(
+        &self,
+        area: Coord<Interval<isize>>,
+        rng: &mut Pcg32,
+    ) -> Iterator<'_, Element, Shuffled<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.select(self.shuffled_scanner(area, rng))
+    }
+
+    /// 返回以随机顺序访问`area`的访问器，见[`Matrix::shuffled`]
+    #[inline]
+    pub fn shuffled_scanner(
+        &self,
+        area: Coord<Interval<isize>>,
+        rng: &mut Pcg32,
+    ) -> Shuffled<CHUNK_WIDTH, CHUNK_HEIGHT> {
+        Shuffled::new(self.size, self.normalize_area(area), rng.split())
+    }
+
+    /// 在`area`中均匀地随机取一个格子
+    #[inline]
+    pub fn random_cell(
+        &self,
+        area: Coord<Interval<isize>>,
+        rng: &mut Pcg32,
+    ) -> (Coord<isize>, &Element) {
+        let area = self.normalize_area(area);
+        let count = measure_area(self.size, area).merge(std::ops::Mul::mul);
+        let pos = area_cell(self.size, area, rng.below(count as u64) as usize);
+        (pos, &self[pos])
+    }
+
+    #[inline]
+    pub fn area(
+        &self,
+        area: Coord<Interval<isize>>,
+    ) -> Iterator<
+        Element,
+        impl DoubleEndedAccessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+        CHUNK_WIDTH,
+        CHUNK_HEIGHT,
+    > {
         self.scan(area)
     }
 
     #[inline]
     pub fn iter(
         &self,
-    ) -> Iterator<Element, impl Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>, CHUNK_WIDTH, CHUNK_HEIGHT>
-    {
+    ) -> Iterator<
+        Element,
+        impl DoubleEndedAccessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+        CHUNK_WIDTH,
+        CHUNK_HEIGHT,
+    > {
         self.area(Coord(0, 0) | (*self.size() - Coord(1, 1)))
     }
 }
@@ -59,6 +224,24 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some((pos, addr)) = self.accessor.next() {
+            debug_assert!(addr < self.matrix.elements.len());
+            // SAFETY: 见`Accessor`的约定
+            Some((pos, unsafe { self.matrix.get_by_addr(addr) }))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'m, Element, Access, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    std::iter::DoubleEndedIterator for Iterator<'m, Element, Access, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Access: DoubleEndedAccessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some((pos, addr)) = self.accessor.next_back() {
+            debug_assert!(addr < self.matrix.elements.len());
+            // SAFETY: 见`DoubleEndedAccessor`的约定
             Some((pos, unsafe { self.matrix.get_by_addr(addr) }))
         } else {
             None
@@ -0,0 +1,68 @@
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 按行遍历`area`，可以修改每个格子
+    #[inline]
+    pub fn area_mut(
+        &mut self,
+        area: Coord<Interval<isize>>,
+    ) -> IterMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        let accessor = self.scanner(area);
+        IterMut {
+            elements: self.elements.as_mut_ptr(),
+            accessor,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.area_mut(Coord(0, 0) | (*self.size() - Coord(1, 1)))
+    }
+}
+
+/// 可以修改格子的迭代器，见[`Matrix::area_mut`]
+///
+/// 只使用[`Scan`]，它不会两次访问同一个格子，所以同时存在的`&mut Element`不会重叠。
+pub struct IterMut<'m, Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    elements: *mut Element,
+    accessor: Scan<CHUNK_WIDTH, CHUNK_HEIGHT>,
+    _marker: std::marker::PhantomData<&'m mut Element>,
+}
+
+impl<'m, Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::iter::Iterator
+    for IterMut<'m, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    type Item = (Coord<isize>, &'m mut Element);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pos, addr) = self.accessor.next()?;
+        // SAFETY: 见`Accessor`的约定；`Scan`给出的偏移量互不相同
+        Some((pos, unsafe { &mut *self.elements.add(addr) }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.accessor.len(), Some(self.accessor.len()))
+    }
+}
+
+impl<'m, Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    std::iter::DoubleEndedIterator for IterMut<'m, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (pos, addr) = self.accessor.next_back()?;
+        // SAFETY: 同`next`
+        Some((pos, unsafe { &mut *self.elements.add(addr) }))
+    }
+}
+
+impl<'m, Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::iter::ExactSizeIterator
+    for IterMut<'m, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+}
+
+impl<'m, Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::iter::FusedIterator
+    for IterMut<'m, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+}
@@ -0,0 +1,379 @@
+/// 可以撤销和重做修改的[`Matrix`]
+///
+/// 通过[`Deref`](std::ops::Deref)可以使用[`Matrix`]的所有只读方法；
+/// 所有修改都经过[`Journaled`]，修改前的值会被保存下来。
+///
+/// 修改按事务分组：[`begin`](Journaled::begin)和[`commit`](Journaled::commit)之间的修改是一个事务。
+/// 事务之外的修改放在一个不具名的事务中，到下一次[`begin`](Journaled::begin)、
+/// [`undo`](Journaled::undo)或[`redo`](Journaled::redo)时自动提交。
+///
+/// 提交的事务以[`Patch`]的形式保存，所有可撤销的事务一共最多保存`budget`个格子，
+/// 超出时丢弃最早的事务。
+pub struct Journaled<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    matrix: Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+    open: Option<Transaction<Element>>,
+    undo: std::collections::VecDeque<(String, Patch<Element>)>,
+    redo: Vec<(String, Patch<Element>)>,
+    /// `undo`中保存的格子数
+    used: usize,
+    budget: usize,
+}
+
+/// 没有提交的事务
+struct Transaction<Element> {
+    name: String,
+    /// 是否由[`Journaled::begin`]开始
+    explicit: bool,
+    /// 按偏移量排列的，每个格子第一次修改前的值
+    old: std::collections::BTreeMap<usize, (Coord<isize>, Element)>,
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Journaled<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Element: Clone + PartialEq,
+{
+    /// 开始记录`matrix`的修改，可撤销的事务一共最多保存`budget`个格子
+    pub fn new(matrix: Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>, budget: usize) -> Self {
+        Self {
+            matrix,
+            open: None,
+            undo: std::collections::VecDeque::new(),
+            redo: vec![],
+            used: 0,
+            budget,
+        }
+    }
+
+    /// 提交没有提交的修改，返回矩阵
+    pub fn into_inner(mut self) -> Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.flush();
+        self.matrix
+    }
+
+    /// 开始名为`name`的事务
+    ///
+    /// *已经在[`begin`](Journaled::begin)开始的事务中时panic*
+    pub fn begin(&mut self, name: &str) {
+        assert!(!self.in_transaction(), "a transaction is already open");
+        self.flush();
+        self.open = Some(Transaction {
+            name: name.to_string(),
+            explicit: true,
+            old: std::collections::BTreeMap::new(),
+        });
+    }
+
+    /// 提交当前事务，它成为可以撤销的最后一个事务；之前撤销的事务不能再重做。
+    /// 事务没有改变任何格子时不保存
+    ///
+    /// *不在[`begin`](Journaled::begin)开始的事务中时panic*
+    pub fn commit(&mut self) {
+        assert!(self.in_transaction(), "no transaction is open");
+        self.flush();
+    }
+
+    /// 放弃当前事务，恢复事务中修改过的格子
+    ///
+    /// *不在[`begin`](Journaled::begin)开始的事务中时panic*
+    pub fn rollback(&mut self) {
+        assert!(self.in_transaction(), "no transaction is open");
+        let transaction = self.open.take().unwrap();
+        for (addr, (_, old)) in transaction.old {
+            // SAFETY: 偏移量由`record`计算
+            *unsafe { self.matrix.get_by_addr_mut(addr) } = old;
+        }
+    }
+
+    /// 是否在[`begin`](Journaled::begin)开始的事务中
+    #[inline]
+    pub fn in_transaction(&self) -> bool {
+        self.open.as_ref().is_some_and(|t| t.explicit)
+    }
+
+    /// 撤销最后一个事务，返回它的名字；没有可以撤销的事务时返回[`None`]
+    ///
+    /// *在[`begin`](Journaled::begin)开始的事务中时panic*
+    pub fn undo(&mut self) -> Option<String> {
+        assert!(!self.in_transaction(), "cannot undo inside a transaction");
+        self.flush();
+        let (name, patch) = self.undo.pop_back()?;
+        self.used -= patch.len();
+        let inverse = patch
+            .clone()
+            .invert()
+            .expect("journal patches store old values");
+        self.matrix
+            .apply(&inverse)
+            .expect("journal patches match the matrix");
+        self.redo.push((name.clone(), patch));
+        Some(name)
+    }
+
+    /// 重做最后一个撤销的事务，返回它的名字；没有可以重做的事务时返回[`None`]
+    ///
+    /// *在[`begin`](Journaled::begin)开始的事务中时panic*
+    pub fn redo(&mut self) -> Option<String> {
+        assert!(!self.in_transaction(), "cannot redo inside a transaction");
+        self.flush();
+        let (name, patch) = self.redo.pop()?;
+        self.matrix
+            .apply(&patch)
+            .expect("journal patches match the matrix");
+        self.used += patch.len();
+        self.undo.push_back((name.clone(), patch));
+        self.trim();
+        Some(name)
+    }
+
+    /// 可以撤销的事务的名字，最早的在前，不包括没有提交的事务
+    pub fn history(&self) -> impl std::iter::Iterator<Item = &str> {
+        self.undo.iter().map(|(name, _)| name.as_str())
+    }
+
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// 把`pos`处的格子改成`value`
+    #[inline]
+    pub fn set(&mut self, pos: Coord<isize>, value: Element) {
+        self[pos] = value;
+    }
+
+    /// 见[`Matrix::apply`]，补丁修改的格子记录在当前事务中
+    pub fn apply(&mut self, patch: &Patch<Element>) -> Result<(), MatrixError> {
+        check_size(self.matrix.size, patch.size())?;
+        for (pos, value) in patch.cells() {
+            *self.record(pos) = value.clone();
+        }
+        Ok(())
+    }
+
+    /// 在当前事务中记录`pos`处格子的旧值，返回这个格子
+    fn record(&mut self, pos: Coord<isize>) -> &mut Element {
+        let pos = self.matrix.normalize(pos);
+        // SAFETY: `pos`已经标准化
+        let addr = unsafe {
+            Matrix::<Element, CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address_unchecked(
+                self.matrix.size,
+                pos,
+            )
+        };
+        let element = unsafe { self.matrix.get_by_addr_mut(addr) };
+        let transaction = self.open.get_or_insert_with(|| Transaction {
+            name: String::new(),
+            explicit: false,
+            old: std::collections::BTreeMap::new(),
+        });
+        transaction
+            .old
+            .entry(addr)
+            .or_insert_with(|| (pos, element.clone()));
+        element
+    }
+
+    /// 提交没有提交的事务
+    fn flush(&mut self) {
+        let transaction = match self.open.take() {
+            Some(transaction) => transaction,
+            None => return,
+        };
+        let chunk_row_count = Matrix::<Element, CHUNK_WIDTH, CHUNK_HEIGHT>::calc_chunk_size(Coord(
+            self.matrix.size.0 as usize,
+            self.matrix.size.1 as usize,
+        ))
+        .0;
+        let mut patch = Patch::empty(self.matrix.size);
+        for (addr, (pos, old)) in transaction.old {
+            // SAFETY: 偏移量由`record`计算
+            let new = unsafe { self.matrix.get_by_addr(addr) };
+            if *new != old {
+                let chunk = addr / (CHUNK_WIDTH * CHUNK_HEIGHT);
+                let chunk = Coord(
+                    (chunk % chunk_row_count) as isize,
+                    (chunk / chunk_row_count) as isize,
+                );
+                patch.push(chunk, pos, new.clone(), old);
+            }
+        }
+        if patch.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.used += patch.len();
+        self.undo.push_back((transaction.name, patch));
+        self.trim();
+    }
+
+    /// 丢弃最早的事务，直到保存的格子数不超过`budget`
+    fn trim(&mut self) {
+        while self.used > self.budget {
+            let (_, patch) = self.undo.pop_front().unwrap();
+            self.used -= patch.len();
+        }
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::Deref
+    for Journaled<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    type Target = Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.matrix
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::Index<Coord<isize>>
+    for Journaled<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    type Output = Element;
+
+    #[inline]
+    fn index(&self, index: Coord<isize>) -> &Element {
+        &self.matrix[index]
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::IndexMut<Coord<isize>>
+    for Journaled<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Element: Clone + PartialEq,
+{
+    #[inline]
+    fn index_mut(&mut self, index: Coord<isize>) -> &mut Element {
+        self.record(index)
+    }
+}
+
+#[cfg(test)]
+mod journaled_tests {
+    use super::*;
+
+    type J = Journaled<char, 3, 2>;
+
+    fn journaled(budget: usize) -> J {
+        Journaled::new(Matrix::with_fill(&Coord(7, 5), &'.'), budget)
+    }
+
+    fn text(matrix: &J) -> String {
+        matrix.iter().map(|(_, c)| *c).collect()
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut matrix = journaled(100);
+        let blank = text(&matrix);
+
+        matrix.begin("wall");
+        for x in 0..7 {
+            matrix.set(Coord(x, 2), '#');
+        }
+        matrix[Coord(3, 2)] = 'D';
+        matrix.commit();
+        let walled = text(&matrix);
+
+        matrix.begin("tree");
+        matrix[Coord(-1, -1)] = 'T';
+        matrix.commit();
+        let treed = text(&matrix);
+        assert_eq!(matrix.history().collect::<Vec<_>>(), vec!["wall", "tree"]);
+
+        assert_eq!(matrix.undo(), Some("tree".to_string()));
+        assert_eq!(text(&matrix), walled);
+        assert_eq!(matrix.undo(), Some("wall".to_string()));
+        assert_eq!(text(&matrix), blank);
+        assert_eq!(matrix.undo(), None);
+
+        assert_eq!(matrix.redo(), Some("wall".to_string()));
+        assert_eq!(matrix.redo(), Some("tree".to_string()));
+        assert_eq!(text(&matrix), treed);
+        assert_eq!(matrix.redo(), None);
+
+        // 新的修改以后不能再重做
+        matrix.undo();
+        assert!(matrix.can_redo());
+        matrix[Coord(0, 0)] = 'x';
+        matrix[Coord(1, 0)] = 'y';
+        assert_eq!(matrix.undo(), Some(String::new()));
+        assert_eq!(matrix.redo(), Some(String::new()));
+        assert_eq!(matrix.redo(), None);
+        matrix.undo();
+        assert_eq!(text(&matrix), walled);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut matrix = journaled(100);
+        matrix.begin("a");
+        matrix[Coord(1, 1)] = 'a';
+        matrix.commit();
+        let before = text(&matrix);
+
+        matrix.begin("b");
+        matrix[Coord(1, 1)] = 'b';
+        matrix[Coord(1, 1)] = 'c';
+        matrix[Coord(4, 4)] = 'b';
+        matrix.rollback();
+        assert_eq!(text(&matrix), before);
+        assert_eq!(matrix.history().collect::<Vec<_>>(), vec!["a"]);
+
+        // 没有改变任何格子的事务不保存
+        matrix.begin("noop");
+        matrix[Coord(2, 2)] = 'z';
+        matrix[Coord(2, 2)] = '.';
+        matrix.commit();
+        assert_eq!(matrix.history().count(), 1);
+
+        let target = {
+            let mut m = (*matrix).clone();
+            m[Coord(6, 0)] = 'p';
+            m
+        };
+        let patch = matrix.diff(&target).unwrap();
+        matrix.begin("patch");
+        matrix.apply(&patch).unwrap();
+        matrix.commit();
+        assert_eq!(matrix[Coord(6, 0)], 'p');
+        matrix.undo();
+        assert_eq!(text(&matrix), before);
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut matrix = journaled(5);
+        for (i, name) in ["a", "b", "c"].into_iter().enumerate() {
+            matrix.begin(name);
+            matrix[Coord(i as isize, 0)] = 'x';
+            matrix[Coord(i as isize, 1)] = 'x';
+            matrix.commit();
+        }
+        assert_eq!(matrix.history().collect::<Vec<_>>(), vec!["b", "c"]);
+        matrix.undo();
+        matrix.undo();
+        assert_eq!(matrix.undo(), None);
+        assert_eq!(matrix[Coord(0, 0)], 'x');
+        assert_eq!(matrix[Coord(1, 0)], '.');
+
+        // 超过预算的事务不能撤销
+        matrix.begin("huge");
+        for x in 0..6 {
+            matrix[Coord(x, 3)] = 'h';
+        }
+        matrix.commit();
+        assert_eq!(matrix.history().count(), 0);
+        assert_eq!(matrix.into_inner()[Coord(5, 3)], 'h');
+    }
+
+    #[test]
+    #[should_panic(expected = "already open")]
+    fn test_nested_begin() {
+        let mut matrix = journaled(5);
+        matrix.begin("a");
+        matrix.begin("b");
+    }
+}
@@ -0,0 +1,459 @@
+/// 大小和分块都相同的一组[`Matrix`]，每一层用[`SWord`]命名
+///
+/// 每一层的元素类型可以不同。改变大小等操作同时作用于所有的层。
+pub struct Layers<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    size: Coord<isize>,
+    layers: HashMap<SWord, Box<dyn Layer<CHUNK_WIDTH, CHUNK_HEIGHT>>>,
+}
+
+/// 擦除了元素类型的[`Matrix`]
+trait Layer<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    /// 大小改为`size`的副本，见[`Matrix::resize`]；错误见[`Matrix::try_with_ctor`]
+    fn try_resized(
+        &self,
+        size: &Coord<usize>,
+    ) -> Result<Box<dyn Layer<CHUNK_WIDTH, CHUNK_HEIGHT>>, MatrixError>;
+
+    fn snapshot(&self) -> Box<dyn Layer<CHUNK_WIDTH, CHUNK_HEIGHT>>;
+
+    fn as_any(&self) -> &dyn std::any::Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Layer<CHUNK_WIDTH, CHUNK_HEIGHT>
+    for Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Element: Clone + Default + 'static,
+{
+    fn try_resized(
+        &self,
+        size: &Coord<usize>,
+    ) -> Result<Box<dyn Layer<CHUNK_WIDTH, CHUNK_HEIGHT>>, MatrixError> {
+        let old_size = self.size;
+        let resized = Matrix::try_with_ctor(size, |opt_pos| match opt_pos {
+            Some(pos) if pos.0 < old_size.0 && pos.1 < old_size.1 => self[pos].clone(),
+            _ => Element::default(),
+        })?;
+        Ok(Box::new(resized))
+    }
+
+    fn snapshot(&self) -> Box<dyn Layer<CHUNK_WIDTH, CHUNK_HEIGHT>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[allow(dead_code)]
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Layers<CHUNK_WIDTH, CHUNK_HEIGHT> {
+    /// 构造大小为`size`、没有层的容器
+    pub fn new(size: &Coord<usize>) -> Self {
+        Self {
+            size: Coord(size.0 as isize, size.1 as isize),
+            layers: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub const fn size(&self) -> &Coord<isize> {
+        &self.size
+    }
+
+    /// 加入名为`name`的层，同名的层会被替换。
+    ///
+    /// `matrix`的大小不同时返回[`MatrixError::SizeMismatch`]
+    pub fn add<Element>(
+        &mut self,
+        name: SWord,
+        matrix: Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+    ) -> Result<(), MatrixError>
+    where
+        Element: Clone + Default + 'static,
+    {
+        check_size(self.size, matrix.size)?;
+        self.layers.insert(name, Box::new(matrix));
+        Ok(())
+    }
+
+    /// 用`constructor`构造名为`name`的层，见[`Matrix::with_ctor`]
+    pub fn add_with<Element>(
+        &mut self,
+        name: SWord,
+        constructor: impl FnMut(Option<Coord<isize>>) -> Element,
+    ) where
+        Element: Clone + Default + 'static,
+    {
+        let size = Coord(self.size.0 as usize, self.size.1 as usize);
+        self.layers.insert(
+            name,
+            Box::new(Matrix::<Element, CHUNK_WIDTH, CHUNK_HEIGHT>::with_ctor(
+                &size,
+                constructor,
+            )),
+        );
+    }
+
+    /// 删除名为`name`的层，返回是否有这一层
+    pub fn remove(&mut self, name: SWord) -> bool {
+        self.layers.remove(&name).is_some()
+    }
+
+    #[inline]
+    pub fn contains(&self, name: SWord) -> bool {
+        self.layers.contains_key(&name)
+    }
+
+    /// 所有层的名字，顺序不确定
+    pub fn names(&self) -> impl std::iter::Iterator<Item = SWord> + '_ {
+        self.layers.keys().copied()
+    }
+
+    /// 名为`name`的层；没有这一层或者元素类型不是`Element`时返回[`None`]
+    pub fn get<Element: 'static>(
+        &self,
+        name: SWord,
+    ) -> Option<&Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>> {
+        self.layers.get(&name)?.as_any().downcast_ref()
+    }
+
+    /// 见[`Layers::get`]，只能修改格子，不能改变层的大小
+    pub fn get_mut<Element: 'static>(
+        &mut self,
+        name: SWord,
+    ) -> Option<LayerMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT>> {
+        let matrix = self.layers.get_mut(&name)?.as_any_mut().downcast_mut()?;
+        Some(LayerMut { matrix })
+    }
+
+    /// 同时按行遍历两层中的`area`；有一层不存在或类型不对时返回[`None`]
+    pub fn area2<A: 'static, B: 'static>(
+        &self,
+        names: (SWord, SWord),
+        area: Coord<Interval<isize>>,
+    ) -> Option<impl std::iter::Iterator<Item = (Coord<isize>, &A, &B)>> {
+        let a = self.get::<A>(names.0)?;
+        let b = self.get::<B>(names.1)?;
+        let mut scan = a.scanner(area);
+        Some(std::iter::from_fn(move || {
+            let (pos, addr) = scan.next()?;
+            // SAFETY: 所有层的大小和分块都相同，偏移量由`Scan`计算
+            Some(unsafe { (pos, a.get_by_addr(addr), b.get_by_addr(addr)) })
+        }))
+    }
+
+    /// 同时按行遍历三层中的`area`；有一层不存在或类型不对时返回[`None`]
+    pub fn area3<A: 'static, B: 'static, C: 'static>(
+        &self,
+        names: (SWord, SWord, SWord),
+        area: Coord<Interval<isize>>,
+    ) -> Option<impl std::iter::Iterator<Item = (Coord<isize>, &A, &B, &C)>> {
+        let a = self.get::<A>(names.0)?;
+        let b = self.get::<B>(names.1)?;
+        let c = self.get::<C>(names.2)?;
+        let mut scan = a.scanner(area);
+        Some(std::iter::from_fn(move || {
+            let (pos, addr) = scan.next()?;
+            // SAFETY: 同`area2`
+            Some(unsafe {
+                (
+                    pos,
+                    a.get_by_addr(addr),
+                    b.get_by_addr(addr),
+                    c.get_by_addr(addr),
+                )
+            })
+        }))
+    }
+
+    /// 把所有层的大小都改为`size`，见[`Matrix::resize`]
+    ///
+    /// 先构造所有改变大小以后的层，有一层出错时返回错误（见[`Matrix::try_with_ctor`]），
+    /// 所有的层和大小都不变。
+    pub fn resize(&mut self, size: &Coord<usize>) -> Result<(), MatrixError> {
+        let resized = self
+            .layers
+            .iter()
+            .map(|(name, layer)| Ok((*name, layer.try_resized(size)?)))
+            .collect::<Result<Vec<_>, MatrixError>>()?;
+        self.layers.extend(resized);
+        self.size = Coord(size.0 as isize, size.1 as isize);
+        Ok(())
+    }
+
+    /// 复制所有的层
+    pub fn snapshot(&self) -> Self {
+        Self {
+            size: self.size,
+            layers: self
+                .layers
+                .iter()
+                .map(|(name, layer)| (*name, layer.snapshot()))
+                .collect(),
+        }
+    }
+}
+
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Clone
+    for Layers<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    fn clone(&self) -> Self {
+        self.snapshot()
+    }
+}
+
+/// [`Layers::get_mut`]返回的一层
+///
+/// 通过[`Deref`](std::ops::Deref)可以使用[`Matrix`]的所有只读方法；
+/// 只能修改格子，不能替换或改变大小，[`Layers::area2`]等方法依赖所有层的大小相同。
+pub struct LayerMut<'a, Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    matrix: &'a mut Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    LayerMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 见[`Matrix::area_mut`]
+    #[inline]
+    pub fn area_mut(
+        &mut self,
+        area: Coord<Interval<isize>>,
+    ) -> IterMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.matrix.area_mut(area)
+    }
+
+    /// 见[`Matrix::iter_mut`]
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.matrix.iter_mut()
+    }
+
+    /// 见[`Matrix::apply_each`]
+    #[inline]
+    pub fn apply_each(&mut self, f: impl FnMut(Coord<isize>, &mut Element)) {
+        self.matrix.apply_each(f);
+    }
+
+    /// 见[`Matrix::fill_area`]
+    #[inline]
+    pub fn fill_area(&mut self, area: Coord<Interval<isize>>, value: &Element)
+    where
+        Element: Clone,
+    {
+        self.matrix.fill_area(area, value);
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::Deref
+    for LayerMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    type Target = Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.matrix
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::Index<Coord<isize>>
+    for LayerMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    type Output = Element;
+
+    #[inline]
+    fn index(&self, index: Coord<isize>) -> &Element {
+        &self.matrix[index]
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::IndexMut<Coord<isize>>
+    for LayerMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    #[inline]
+    fn index_mut(&mut self, index: Coord<isize>) -> &mut Element {
+        &mut self.matrix[index]
+    }
+}
+
+#[cfg(test)]
+mod layers_tests {
+    use super::*;
+
+    fn world() -> Layers<3, 2> {
+        let mut world = Layers::new(&Coord(5, 4));
+        world.add_with(SWord::new("terrain"), |opt_pos| {
+            opt_pos.map_or(String::new(), |pos| pos.to_string())
+        });
+        world.add_with(SWord::new("moisture"), |opt_pos| {
+            opt_pos.map_or(0, |pos| pos.0 + 10 * pos.1)
+        });
+        world
+            .add(
+                SWord::new("owner"),
+                Matrix::<Option<u8>, 3, 2>::with_fill(&Coord(5, 4), &Some(1)),
+            )
+            .unwrap();
+        world
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut world = world();
+        assert!(world.contains(SWord::new("owner")));
+        assert_eq!(world.names().count(), 3);
+        assert!(world.get::<isize>(SWord::new("moisture")).is_some());
+        assert!(world.get::<i32>(SWord::new("moisture")).is_none());
+        assert!(world.get::<isize>(SWord::new("light")).is_none());
+
+        let error = world.add(SWord::new("light"), Matrix::<u8, 3, 2>::new(&Coord(4, 5)));
+        assert_eq!(
+            error,
+            Err(MatrixError::SizeMismatch {
+                expected: Coord(5, 4),
+                found: Coord(4, 5)
+            })
+        );
+        assert!(!world.contains(SWord::new("light")));
+
+        world.get_mut::<Option<u8>>(SWord::new("owner")).unwrap()[Coord(4, 3)] = None;
+        assert!(world.remove(SWord::new("owner")));
+        assert!(!world.remove(SWord::new("owner")));
+    }
+
+    #[test]
+    fn test_joint_iteration() {
+        let world = world();
+        let names = (
+            SWord::new("terrain"),
+            SWord::new("moisture"),
+            SWord::new("owner"),
+        );
+        let area = Coord(4, 3) | Coord(1, 0);
+        let cells: Vec<_> = world
+            .area3::<String, isize, Option<u8>>(names, area)
+            .unwrap()
+            .map(|(pos, terrain, moisture, owner)| {
+                assert_eq!(*terrain, pos.to_string());
+                assert_eq!(*moisture, pos.0 + 10 * pos.1);
+                assert_eq!(*owner, Some(1));
+                pos
+            })
+            .collect();
+        let expected: Vec<_> = world
+            .get::<String>(names.0)
+            .unwrap()
+            .area(area)
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(cells, expected);
+
+        assert_eq!(
+            world
+                .area2::<isize, String>((names.1, names.0), area)
+                .unwrap()
+                .count(),
+            6
+        );
+        assert!(world
+            .area2::<isize, isize>((names.1, names.0), area)
+            .is_none());
+    }
+
+    #[test]
+    fn test_resize_and_snapshot() {
+        let mut world = world();
+        let snapshot = world.snapshot();
+        assert_eq!(
+            world.resize(&Coord(0, 3)),
+            Err(MatrixError::ZeroSize {
+                size: Coord(0, 3).into()
+            })
+        );
+        assert_eq!(
+            world.resize(&Coord(usize::MAX, 2)),
+            Err(MatrixError::SizeOverflow {
+                size: Coord(usize::MAX, 2).into()
+            })
+        );
+        assert_eq!(*world.size(), Coord(5, 4));
+        assert_eq!(
+            *world.get::<isize>(SWord::new("moisture")).unwrap().size(),
+            Coord(5, 4)
+        );
+        world.resize(&Coord(7, 2)).unwrap();
+        assert_eq!(*world.size(), Coord(7, 2));
+        assert_eq!(*snapshot.size(), Coord(5, 4));
+
+        let terrain = world.get::<String>(SWord::new("terrain")).unwrap();
+        let moisture = world.get::<isize>(SWord::new("moisture")).unwrap();
+        let owner = world.get::<Option<u8>>(SWord::new("owner")).unwrap();
+        for layer_size in [*terrain.size(), *moisture.size(), *owner.size()] {
+            assert_eq!(layer_size, Coord(7, 2));
+        }
+        for (pos, value) in terrain.iter() {
+            if pos.0 < 5 {
+                assert_eq!(*value, pos.to_string());
+                assert_eq!(moisture[pos], pos.0 + 10 * pos.1);
+                assert_eq!(owner[pos], Some(1));
+            } else {
+                assert_eq!(*value, "");
+                assert_eq!(moisture[pos], 0);
+                assert_eq!(owner[pos], None);
+            }
+        }
+
+        let old = snapshot.get::<String>(SWord::new("terrain")).unwrap();
+        assert_eq!(old[Coord(4, 3)], "(4, 3)");
+        assert!(world
+            .add(SWord::new("light"), Matrix::<u8, 3, 2>::new(&Coord(7, 2)))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_get_mut_keeps_size() {
+        let (a, b) = (SWord::new("a"), SWord::new("b"));
+        let mut world = Layers::<3, 2>::new(&Coord(30, 20));
+        world.add_with(a, |opt_pos| opt_pos.map_or(0, |pos| pos.0 * pos.1));
+        world.add_with(b, |_| 0u8);
+
+        let mut layer = world.get_mut::<u8>(b).unwrap();
+        layer[Coord(29, 19)] = 1;
+        layer.fill_area(Coord(0, 0) | Coord(1, 1), &2);
+        for (_, value) in layer.area_mut(Coord(-1, 0) | Coord(-1, -1)) {
+            *value += 3;
+        }
+        assert_eq!(*layer.size(), Coord(30, 20));
+
+        let whole = Coord(0, 0) | Coord(29, 19);
+        let sum: usize = world
+            .area2::<isize, u8>((a, b), whole)
+            .unwrap()
+            .map(|(pos, a, b)| {
+                assert_eq!(*a, pos.0 * pos.1);
+                *b as usize
+            })
+            .sum();
+        assert_eq!(sum, 4 * 2 + 20 * 3 + 1);
+
+        world.resize(&Coord(1, 1)).unwrap();
+        let cells: Vec<_> = world
+            .area2::<isize, u8>((a, b), whole)
+            .unwrap()
+            .map(|(pos, a, b)| (pos, *a, *b))
+            .collect();
+        let expected: Vec<_> = world
+            .get::<isize>(a)
+            .unwrap()
+            .area(whole)
+            .map(|(pos, _)| (pos, 0, 2))
+            .collect();
+        assert_eq!(cells, expected);
+    }
+}
@@ -0,0 +1,167 @@
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 用`f(pos, &元素)`的结果构造同样大小的新矩阵。
+    ///
+    /// 按存储顺序逐块遍历，不使用的区域不调用`f`，用`(0, 0)`处结果的副本填充
+    pub fn map<U: Clone>(
+        &self,
+        f: impl FnMut(Coord<isize>, &Element) -> U,
+    ) -> Matrix<U, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        Matrix {
+            elements: Self::map_elements(self.size, self.elements.iter(), f),
+            size: self.size,
+        }
+    }
+
+    /// 同[`Matrix::map`]，但是按值取走矩阵中的元素
+    pub fn into_map<U: Clone>(
+        self,
+        f: impl FnMut(Coord<isize>, Element) -> U,
+    ) -> Matrix<U, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        Matrix {
+            elements: Self::map_elements(self.size, self.elements.into_iter(), f),
+            size: self.size,
+        }
+    }
+
+    /// 用`f(pos, &本矩阵的元素, &other的元素)`的结果构造同样大小的新矩阵，不使用的区域同[`Matrix::map`]。
+    ///
+    /// 两个矩阵大小不同时返回[`MatrixError::SizeMismatch`]
+    pub fn zip_with<Other, U: Clone>(
+        &self,
+        other: &Matrix<Other, CHUNK_WIDTH, CHUNK_HEIGHT>,
+        mut f: impl FnMut(Coord<isize>, &Element, &Other) -> U,
+    ) -> Result<Matrix<U, CHUNK_WIDTH, CHUNK_HEIGHT>, MatrixError> {
+        check_size(self.size, other.size)?;
+        Ok(Matrix {
+            elements: Self::map_elements(
+                self.size,
+                self.elements.iter().zip(other.elements.iter()),
+                |pos, (a, b)| f(pos, a, b),
+            ),
+            size: self.size,
+        })
+    }
+
+    /// 按存储顺序逐块对每个元素调用`f(pos, &mut 元素)`，不使用的区域不调用。
+    ///
+    /// 就是逐格修改的`apply`，因为[`Matrix::apply`]已经用来应用补丁，所以叫`apply_each`
+    pub fn apply_each(&mut self, mut f: impl FnMut(Coord<isize>, &mut Element)) {
+        let size = self.size;
+        for (addr, element) in self.elements.iter_mut().enumerate() {
+            if let Some(pos) = Self::pos_in_size(size, addr) {
+                f(pos, element);
+            }
+        }
+    }
+
+    /// 按存储顺序对`items`中矩阵内的格子调用`f`，不使用的区域填充第一个结果的副本
+    fn map_elements<T, U: Clone>(
+        size: Coord<isize>,
+        items: impl std::iter::Iterator<Item = T>,
+        mut f: impl FnMut(Coord<isize>, T) -> U,
+    ) -> Vec<U> {
+        let mut padding: Option<U> = None;
+        items
+            .enumerate()
+            .map(|(addr, item)| match Self::pos_in_size(size, addr) {
+                Some(pos) => {
+                    let value = f(pos, item);
+                    if padding.is_none() {
+                        padding = Some(value.clone());
+                    }
+                    value
+                }
+                // 偏移量0总是`(0, 0)`，所以这时已经有第一个结果
+                None => padding.clone().unwrap(),
+            })
+            .collect()
+    }
+
+    /// 偏移量`addr`对应的位置，在不使用的区域时返回`None`
+    #[inline]
+    fn pos_in_size(size: Coord<isize>, addr: usize) -> Option<Coord<isize>> {
+        let pos = unsafe { Self::pos_at_unchecked(size, addr) };
+        (pos.0 < size.0 && pos.1 < size.1).then_some(pos)
+    }
+}
+
+#[cfg(test)]
+mod map_tests {
+    use super::*;
+
+    fn source<const CW: usize, const CH: usize>() -> Matrix<i32, CW, CH> {
+        Matrix::with_ctor(&Coord(7, 5), |opt_pos| {
+            opt_pos.map_or(-1, |pos| (pos.0 + 10 * pos.1) as i32)
+        })
+    }
+
+    fn check<const CW: usize, const CH: usize>() {
+        let matrix = source::<CW, CH>();
+        let mut calls = 0;
+        let mask = matrix.map(|pos, value| {
+            calls += 1;
+            assert_eq!(*value, (pos.0 + 10 * pos.1) as i32);
+            value % 3 == 0
+        });
+        assert_eq!(calls, 35);
+        for (pos, value) in matrix.iter() {
+            assert_eq!(mask[pos], value % 3 == 0);
+        }
+
+        let doubled = matrix.zip_with(&mask, |_, a, b| if *b { *a * 2 } else { 0 });
+        let doubled = doubled.unwrap();
+        for (pos, value) in matrix.iter() {
+            assert_eq!(doubled[pos], if mask[pos] { value * 2 } else { 0 });
+        }
+
+        let mut applied = matrix.clone();
+        applied.apply_each(|pos, value| *value += pos.1 as i32);
+        let strings = applied.into_map(|pos, value| {
+            assert_eq!(value, matrix[pos] + pos.1 as i32);
+            value.to_string()
+        });
+        assert_eq!(strings[Coord(6, 4)], "50");
+    }
+
+    #[test]
+    fn test_map() {
+        check::<1, 1>();
+        check::<2, 3>();
+        check::<4, 4>();
+        check::<8, 2>();
+    }
+
+    #[test]
+    fn test_padding_without_default() {
+        #[derive(Clone, PartialEq, Debug)]
+        struct Label(isize);
+
+        let matrix = source::<3, 4>();
+        let labels = matrix.map(|pos, _| Label(pos.0 + 10 * pos.1));
+        assert_eq!(labels[Coord(6, 4)], Label(46));
+        // 不使用的区域是`(0, 0)`处结果的副本
+        assert!(labels.elements.iter().all(|label| label.0 >= 0));
+        let count = labels
+            .elements
+            .iter()
+            .filter(|label| **label == Label(0))
+            .count();
+        assert_eq!(count, labels.elements.len() - 35 + 1);
+    }
+
+    #[test]
+    fn test_zip_with_size_mismatch() {
+        let matrix = source::<2, 2>();
+        let other = Matrix::<u8, 2, 2>::new(&Coord(5, 7));
+        assert_eq!(
+            matrix.zip_with(&other, |_, _, _| 0u8).err(),
+            Some(MatrixError::SizeMismatch {
+                expected: Coord(7, 5),
+                found: Coord(5, 7)
+            })
+        );
+    }
+}
@@ -16,13 +16,25 @@ impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
     /// - `constructor(Some(Coord(x, y)))`的值填充`(x, y)`对映的位置
     /// - 不使用的区域用`constructor(None)`的值填充
     ///  
-    /// *`size.0`或`size.1`超过[`isize::MAX`]引发未定义行为。*
+    /// *`size`不合法时panic，见[`Matrix::try_with_ctor`]*
     #[inline]
     pub fn with_ctor(
         size: &Coord<usize>,
-        mut constructor: impl FnMut(Option<Coord<isize>>) -> Element,
+        constructor: impl FnMut(Option<Coord<isize>>) -> Element,
     ) -> Self {
-        let alloc_size = Self::calc_alloc_size(*size);
+        Self::try_with_ctor(size, constructor).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// 同[`Matrix::with_ctor`]，以下情况返回错误：
+    /// - `size`的任何一个维度是0：[`MatrixError::ZeroSize`]
+    /// - `CHUNK_WIDTH`或`CHUNK_HEIGHT`是0：[`MatrixError::ZeroChunkSize`]
+    /// - `size`的任何一个维度超过[`isize::MAX`]：[`MatrixError::SizeOverflow`]
+    /// - 需要分配的空间超过[`isize::MAX`]字节：[`MatrixError::AllocationOverflow`]
+    pub fn try_with_ctor(
+        size: &Coord<usize>,
+        mut constructor: impl FnMut(Option<Coord<isize>>) -> Element,
+    ) -> Result<Self, MatrixError> {
+        let alloc_size = Self::check_alloc_size(*size)?;
         let mut instance = Self {
             elements: Vec::with_capacity(alloc_size),
             size: Coord(size.0 as isize, size.1 as isize),
@@ -34,24 +46,29 @@ impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
             let new_element = constructor(verified_pos);
             instance.elements.push(new_element);
         }
-        instance
+        Ok(instance)
     }
 
+    /// 用二维数组构造大小为`Coord(X, Y)`的矩阵，`elements[y][x]`填充`(x, y)`对映的位置，
+    /// 不使用的区域用`none()`的值填充
+    ///
+    /// *`X`或`Y`是0时panic，见[`Matrix::try_with`]*
     #[inline]
     pub fn with<const X: usize, const Y: usize>(
-        mut elements: [[Element; X]; Y],
+        elements: [[Element; X]; Y],
         none: impl Fn() -> Element,
     ) -> Self {
-        Self::with_ctor(&Coord(X, Y), |opt_pos| {
-            if let Some(pos) = opt_pos {
-                if pos >= Coord(0, 0) && pos < Coord(X as isize, Y as isize) {
-                    return std::mem::replace(
-                        &mut elements[pos.1 as usize][pos.0 as usize],
-                        none(),
-                    );
-                }
-            }
-            none()
+        Self::try_with(elements, none).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// 同[`Matrix::with`]，错误见[`Matrix::try_with_ctor`]
+    pub fn try_with<const X: usize, const Y: usize>(
+        mut elements: [[Element; X]; Y],
+        none: impl Fn() -> Element,
+    ) -> Result<Self, MatrixError> {
+        Self::try_with_ctor(&Coord(X, Y), |opt_pos| match opt_pos {
+            Some(pos) => std::mem::replace(&mut elements[pos.1 as usize][pos.0 as usize], none()),
+            None => none(),
         })
     }
 
@@ -71,6 +88,12 @@ impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
     pub fn normalize_area(&self, area: Coord<Interval<isize>>) -> Coord<Interval<isize>> {
         self.normalize(area.from()) | self.normalize(area.to())
     }
+
+    /// 返回和矩阵大小相同的[`Torus`]，用于考虑环绕的距离计算
+    #[inline]
+    pub const fn torus(&self) -> Torus {
+        Torus::new(self.size)
+    }
 }
 
 #[allow(dead_code)]
@@ -81,10 +104,32 @@ where
 {
     /// 构造大小为参数`size`的矩阵。  
     /// 矩阵的所有元素由`Element::default()`的结果填充。  
+    ///
+    /// *`size`不合法时panic，见[`Matrix::try_with_ctor`]*
     #[inline]
     pub fn new(size: &Coord<usize>) -> Self {
         Self::with_ctor(size, |_| Element::default())
     }
+
+    /// 同[`Matrix::new`]，错误见[`Matrix::try_with_ctor`]
+    #[inline]
+    pub fn try_new(size: &Coord<usize>) -> Result<Self, MatrixError> {
+        Self::try_with_ctor(size, |_| Element::default())
+    }
+
+    /// 把矩阵的大小改为`size`。  
+    /// 新旧大小都包含的格子保留原来的值，其余的格子由`Element::default()`填充。  
+    pub fn resize(&mut self, size: &Coord<usize>) {
+        let old_size = self.size;
+        *self = Self::with_ctor(size, |opt_pos| match opt_pos {
+            Some(pos) if pos.0 < old_size.0 && pos.1 < old_size.1 => {
+                // SAFETY: `pos`在旧的矩阵之内
+                let addr = unsafe { Self::calc_address_unchecked(old_size, pos) };
+                std::mem::take(unsafe { self.get_by_addr_mut(addr) })
+            }
+            _ => Element::default(),
+        });
+    }
 }
 
 impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::Index<Coord<isize>>
@@ -123,11 +168,17 @@ where
     /// 构造大小为参数`size`的矩阵。  
     /// 矩阵中所有的元素由`element.clone()`的结果填充  
     ///   
-    /// *`size.0`或`size.1`超过[`isize::MAX`]引发未定义行为。*
+    /// *`size`不合法时panic，见[`Matrix::try_with_ctor`]*
     #[inline]
     pub fn with_fill(size: &Coord<usize>, element: &Element) -> Self {
         Self::with_ctor(size, |_| element.clone())
     }
+
+    /// 同[`Matrix::with_fill`]，错误见[`Matrix::try_with_ctor`]
+    #[inline]
+    pub fn try_with_fill(size: &Coord<usize>, element: &Element) -> Result<Self, MatrixError> {
+        Self::try_with_ctor(size, |_| element.clone())
+    }
 }
 
 impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Clone
@@ -150,17 +201,17 @@ impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
 {
     #[inline]
     const fn calc_chunk_size(size: Coord<usize>) -> Coord<usize> {
-        let chunk_row_count = (size.0 - 1) / CHUNK_WIDTH + 1;
-        let chunk_col_count = (size.1 - 1) / CHUNK_HEIGHT + 1;
-        Coord(chunk_row_count, chunk_col_count)
+        Coord(size.0.div_ceil(CHUNK_WIDTH), size.1.div_ceil(CHUNK_HEIGHT))
     }
 
-    /// 计算`size`大小的矩阵需要分配多长的数组才能存下
+    /// 检查`size`大小的矩阵能否构造，返回需要分配的数组长度
     #[inline]
-    const fn calc_alloc_size(size: Coord<usize>) -> usize {
-        let chunk_size = Self::calc_chunk_size(size);
-        let chunk_count = chunk_size.0 * chunk_size.1;
-        CHUNK_WIDTH * CHUNK_HEIGHT * chunk_count
+    fn check_alloc_size(size: Coord<usize>) -> Result<usize, MatrixError> {
+        check_chunked_alloc_size(
+            size,
+            Coord(CHUNK_WIDTH, CHUNK_HEIGHT),
+            std::mem::size_of::<Element>(),
+        )
     }
 
     /// 计算在`size`大小的矩阵中，偏移量`addr`对应的位置  
@@ -198,6 +249,13 @@ impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
         chunk_address * CHUNK_WIDTH * CHUNK_HEIGHT + grid_address
     }
 
+    /// 计算在`size`大小矩阵中，位置`at`标准化以后对应的偏移量  
+    /// `size`任何一个维度必须为正  
+    #[inline]
+    fn calc_address(size: Coord<isize>, at: Coord<isize>) -> usize {
+        unsafe { Self::calc_address_unchecked(size, Self::normalize_pos(size, at)) }
+    }
+
     #[inline]
     unsafe fn get_by_addr(&self, addr: usize) -> &Element {
         self.elements.get_unchecked(addr)
@@ -210,8 +268,42 @@ impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
 
     #[inline]
     fn normalize_pos(size: Coord<isize>, pos: Coord<isize>) -> Coord<isize> {
-        pos.reduce(size, isize::rem_euclid)
+        Torus::new(size).normalize(pos)
+    }
+}
+
+/// 检查按`chunk`分块、`size`大小、元素占`element_size`字节的矩阵能否构造，
+/// 返回需要分配的数组长度；二维和三维的矩阵共用。错误见[`Matrix::try_with_ctor`]
+fn check_chunked_alloc_size(
+    size: impl Into<Dims>,
+    chunk: impl Into<Dims>,
+    element_size: usize,
+) -> Result<usize, MatrixError> {
+    let (size, chunk) = (size.into(), chunk.into());
+    let (lens, chunk_lens) = (size.lens(), chunk.lens());
+    if chunk_lens.contains(&0) {
+        return Err(MatrixError::ZeroChunkSize { chunk });
     }
+    if lens.contains(&0) {
+        return Err(MatrixError::ZeroSize { size });
+    }
+    if lens.iter().any(|len| *len > isize::MAX as usize) {
+        return Err(MatrixError::SizeOverflow { size });
+    }
+    // 不使用的区域中的位置也要能用`isize`表示
+    lens.iter()
+        .zip(chunk_lens)
+        .map(|(len, chunk)| {
+            len.div_ceil(chunk)
+                .checked_mul(chunk)
+                .filter(|padded| *padded <= isize::MAX as usize)
+        })
+        .try_fold(1usize, |total, padded| total.checked_mul(padded?))
+        .filter(|len| {
+            len.checked_mul(element_size)
+                .is_some_and(|bytes| bytes <= isize::MAX as usize)
+        })
+        .ok_or(MatrixError::AllocationOverflow { size })
 }
 
 // tests
@@ -249,3 +341,55 @@ fn test() {
     test_sub::<1, 1>();
     test_sub::<2, 2>();
 }
+
+#[cfg(test)]
+#[test]
+fn test_try_constructors() {
+    type M = Matrix<u8, 2, 3>;
+    assert!(M::try_new(&Coord(3, 4)).is_ok());
+    assert_eq!(
+        M::try_new(&Coord(0, 4)).err(),
+        Some(MatrixError::ZeroSize {
+            size: Coord(0, 4).into()
+        })
+    );
+    assert_eq!(
+        M::try_with_fill(&Coord(usize::MAX, 1), &0).err(),
+        Some(MatrixError::SizeOverflow {
+            size: Coord(usize::MAX, 1).into()
+        })
+    );
+    let huge = Coord(isize::MAX as usize, 2);
+    assert_eq!(
+        M::try_with_ctor(&huge, |_| 0).err(),
+        Some(MatrixError::AllocationOverflow { size: huge.into() })
+    );
+    let wide = Coord(1 << 31, 1 << 31);
+    assert_eq!(
+        Matrix::<u64, 1, 1>::try_new(&wide).err(),
+        Some(MatrixError::AllocationOverflow { size: wide.into() })
+    );
+    assert_eq!(
+        Matrix::<u8, 0, 3>::try_new(&Coord(1, 1)).err(),
+        Some(MatrixError::ZeroChunkSize {
+            chunk: Coord(0, 3).into()
+        })
+    );
+
+    let matrix = M::try_with([[1, 2, 3], [4, 5, 6]], || 0).unwrap();
+    assert_eq!(*matrix.size(), Coord(3, 2));
+    assert_eq!(matrix[Coord(2, 1)], 6);
+    assert_eq!(
+        M::try_with::<0, 2>([[], []], || 0).err(),
+        Some(MatrixError::ZeroSize {
+            size: Coord(0, 2).into()
+        })
+    );
+}
+
+#[cfg(test)]
+#[test]
+#[should_panic(expected = "matrix size must not be zero")]
+fn test_zero_size_panics() {
+    Matrix::<u8, 2, 2>::new(&Coord(3, 0));
+}
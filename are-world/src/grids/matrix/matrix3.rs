@@ -0,0 +1,582 @@
+/// 每个维度越界时的处理方式，见[`Matrix3`]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Boundary {
+    /// 两端相连，和[`Matrix`]一样
+    Wrap,
+    /// 越界的位置取最近的边缘
+    Clamp,
+}
+
+impl Boundary {
+    /// 把`value`变换到`0`和`size - 1`之间
+    #[inline]
+    fn apply(self, size: isize, value: isize) -> isize {
+        match self {
+            Boundary::Wrap => value.rem_euclid(size),
+            Boundary::Clamp => value.clamp(0, size - 1),
+        }
+    }
+}
+
+/// 分块存储的三维矩阵，每个维度可以分别选择环绕或者截断，见[`Boundary`]
+///
+/// 存储方式和[`Matrix`]相同：先按块排列，块内按`x`、`y`、`z`的顺序排列。
+/// [`Matrix3::layer`]取出`z`固定的一层，可以当作二维矩阵打印或者遍历。
+pub struct Matrix3<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize> {
+    elements: Vec<Element>,
+    size: Coord3<isize>,
+    /// 每个维度有多少块
+    chunk_count: Coord3<usize>,
+    boundary: Coord3<Boundary>,
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+    /// 构造大小为参数`size`的矩阵。
+    /// 矩阵中的元素由参数函数`constructor`决定：
+    /// - `constructor(Some(Coord3(x, y, z)))`的值填充`(x, y, z)`对映的位置
+    /// - 不使用的区域用`constructor(None)`的值填充
+    ///
+    /// *不能构造的时候panic，见[`Matrix3::try_with_ctor`]*
+    pub fn with_ctor(
+        size: &Coord3<usize>,
+        boundary: Coord3<Boundary>,
+        constructor: impl FnMut(Option<Coord3<isize>>) -> Element,
+    ) -> Self {
+        Self::try_with_ctor(size, boundary, constructor).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// 同[`Matrix3::with_ctor`]，不能构造的时候返回错误：
+    /// - `size`的任何一个维度是0：[`MatrixError::ZeroSize`]
+    /// - 分块大小的任何一个维度是0：[`MatrixError::ZeroChunkSize`]
+    /// - `size`的任何一个维度超过[`isize::MAX`]：[`MatrixError::SizeOverflow`]
+    /// - 需要分配的空间超过[`isize::MAX`]字节：[`MatrixError::AllocationOverflow`]
+    pub fn try_with_ctor(
+        size: &Coord3<usize>,
+        boundary: Coord3<Boundary>,
+        mut constructor: impl FnMut(Option<Coord3<isize>>) -> Element,
+    ) -> Result<Self, MatrixError> {
+        let alloc_size = Self::check_alloc_size(*size)?;
+        let chunk_count = Self::calc_chunk_count(*size);
+        let size = size.map(|s| s as isize);
+        let mut elements = Vec::with_capacity(alloc_size);
+        for addr in 0..alloc_size {
+            let pos = Self::pos_at(chunk_count, addr);
+            let contains = pos.0 < size.0 && pos.1 < size.1 && pos.2 < size.2;
+            elements.push(constructor(if contains { Some(pos) } else { None }));
+        }
+        Ok(Self {
+            elements,
+            size,
+            chunk_count,
+            boundary,
+        })
+    }
+
+    #[inline]
+    pub fn size(&self) -> &Coord3<isize> {
+        &self.size
+    }
+
+    #[inline]
+    pub fn boundary(&self) -> Coord3<Boundary> {
+        self.boundary
+    }
+
+    /// 按每个维度的[`Boundary`]把`pos`变换到矩阵中
+    #[inline]
+    pub fn normalize(&self, pos: Coord3<isize>) -> Coord3<isize> {
+        Coord3(
+            self.boundary.0.apply(self.size.0, pos.0),
+            self.boundary.1.apply(self.size.1, pos.1),
+            self.boundary.2.apply(self.size.2, pos.2),
+        )
+    }
+
+    /// 遍历`area`，见[`Scan3`]
+    #[inline]
+    pub fn area(
+        &self,
+        area: Coord3<Interval<isize>>,
+    ) -> Scan3<'_, Element, CHUNK_X, CHUNK_Y, CHUNK_Z> {
+        Scan3::new(self, area)
+    }
+
+    /// 遍历整个矩阵，见[`Scan3`]
+    #[inline]
+    pub fn iter(&self) -> Scan3<'_, Element, CHUNK_X, CHUNK_Y, CHUNK_Z> {
+        self.area(Coord3(0, 0, 0) | (self.size - Coord3(1, 1, 1)))
+    }
+
+    /// `z`固定的一层，`z`会先按[`Boundary`]变换
+    #[inline]
+    pub fn layer(&self, z: isize) -> MatrixView<'_, Element, CHUNK_X, CHUNK_Y, CHUNK_Z> {
+        MatrixView {
+            matrix: self,
+            z: self.boundary.2.apply(self.size.2, z),
+        }
+    }
+
+    /// 从下到上的每一层
+    #[inline]
+    pub fn layers(
+        &self,
+    ) -> impl std::iter::Iterator<Item = MatrixView<'_, Element, CHUNK_X, CHUNK_Y, CHUNK_Z>> {
+        (0..self.size.2).map(move |z| self.layer(z))
+    }
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+where
+    Element: Default,
+{
+    /// 构造大小为参数`size`的矩阵，所有元素由`Element::default()`的结果填充。
+    ///
+    /// *见[`Matrix3::with_ctor`]*
+    #[inline]
+    pub fn new(size: &Coord3<usize>, boundary: Coord3<Boundary>) -> Self {
+        Self::with_ctor(size, boundary, |_| Element::default())
+    }
+
+    /// 同[`Matrix3::new`]，错误见[`Matrix3::try_with_ctor`]
+    #[inline]
+    pub fn try_new(size: &Coord3<usize>, boundary: Coord3<Boundary>) -> Result<Self, MatrixError> {
+        Self::try_with_ctor(size, boundary, |_| Element::default())
+    }
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+where
+    Element: Clone,
+{
+    /// 构造大小为参数`size`的矩阵，所有元素由`element.clone()`的结果填充。
+    ///
+    /// *见[`Matrix3::with_ctor`]*
+    #[inline]
+    pub fn with_fill(size: &Coord3<usize>, boundary: Coord3<Boundary>, element: &Element) -> Self {
+        Self::with_ctor(size, boundary, |_| element.clone())
+    }
+}
+
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize> Clone
+    for Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+where
+    Element: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            elements: self.elements.clone(),
+            size: self.size,
+            chunk_count: self.chunk_count,
+            boundary: self.boundary,
+        }
+    }
+}
+
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    std::ops::Index<Coord3<isize>> for Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+    type Output = Element;
+
+    /// `index`会先按[`Boundary`]变换
+    #[inline]
+    fn index(&self, index: Coord3<isize>) -> &Self::Output {
+        let addr = Self::calc_address(self.chunk_count, self.normalize(index));
+        &self.elements[addr]
+    }
+}
+
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    std::ops::IndexMut<Coord3<isize>> for Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+    /// `index`会先按[`Boundary`]变换
+    #[inline]
+    fn index_mut(&mut self, index: Coord3<isize>) -> &mut Self::Output {
+        let addr = Self::calc_address(self.chunk_count, self.normalize(index));
+        &mut self.elements[addr]
+    }
+}
+
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize> std::fmt::Display
+    for Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+where
+    Element: std::string::ToString,
+{
+    /// 从下到上依次打印每一层，层之间空一行，见[`MatrixView`]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (z, layer) in self.layers().enumerate() {
+            if z != 0 {
+                write!(f, "{}{}", LINE_DELIM, LINE_DELIM)?;
+            }
+            write!(f, "{}", layer)?;
+        }
+        Ok(())
+    }
+}
+
+// private
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+    /// 每个维度有多少块
+    #[inline]
+    fn calc_chunk_count(size: Coord3<usize>) -> Coord3<usize> {
+        size.reduce(Coord3(CHUNK_X, CHUNK_Y, CHUNK_Z), usize::div_ceil)
+    }
+
+    /// 检查`size`大小的矩阵能否构造，返回需要分配的数组长度
+    fn check_alloc_size(size: Coord3<usize>) -> Result<usize, MatrixError> {
+        check_chunked_alloc_size(
+            size,
+            Coord3(CHUNK_X, CHUNK_Y, CHUNK_Z),
+            std::mem::size_of::<Element>(),
+        )
+    }
+
+    /// 计算在每个维度有`count`块的矩阵中，偏移量`addr`对应的位置，结果可能在不使用的区域里
+    #[inline]
+    fn pos_at(count: Coord3<usize>, addr: usize) -> Coord3<isize> {
+        let chunk_len = CHUNK_X * CHUNK_Y * CHUNK_Z;
+        let (chunk_addr, grid_addr) = (addr / chunk_len, addr % chunk_len);
+        let q = Coord3(
+            chunk_addr % count.0,
+            chunk_addr / count.0 % count.1,
+            chunk_addr / count.0 / count.1,
+        );
+        let r = Coord3(
+            grid_addr % CHUNK_X,
+            grid_addr / CHUNK_X % CHUNK_Y,
+            grid_addr / CHUNK_X / CHUNK_Y,
+        );
+        (q * Coord3(CHUNK_X, CHUNK_Y, CHUNK_Z) + r).map(|v| v as isize)
+    }
+
+    /// 计算在每个维度有`count`块的矩阵中，已经变换到矩阵中的位置`at`对应的偏移量
+    #[inline]
+    fn calc_address(count: Coord3<usize>, at: Coord3<isize>) -> usize {
+        let at = at.map(|v| v as usize);
+        let q = at / Coord3(CHUNK_X, CHUNK_Y, CHUNK_Z);
+        let r = at % Coord3(CHUNK_X, CHUNK_Y, CHUNK_Z);
+        let chunk_addr = (q.2 * count.1 + q.1) * count.0 + q.0;
+        let grid_addr = (r.2 * CHUNK_Y + r.1) * CHUNK_X + r.0;
+        chunk_addr * CHUNK_X * CHUNK_Y * CHUNK_Z + grid_addr
+    }
+}
+
+/// 遍历[`Matrix3`]中一个三维区间的迭代器，见[`Matrix3::area`]
+///
+/// 先沿`x`，再沿`y`，最后沿`z`遍历。
+/// 环绕的维度上，`from > to`的区间跨过边界，见[`Interval`]；
+/// 截断的维度上，区间的两端先截断到矩阵中，`from > to`的区间是空的。
+pub struct Scan3<'m, Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize> {
+    matrix: &'m Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>,
+    from: Coord3<isize>,
+    length: Coord3<isize>,
+    offset: Coord3<isize>,
+    remaining: usize,
+}
+
+impl<'m, Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    Scan3<'m, Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+    fn new(
+        matrix: &'m Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>,
+        area: Coord3<Interval<isize>>,
+    ) -> Self {
+        let from = matrix.normalize(area.from());
+        let to = matrix.normalize(area.to());
+        let boundary = matrix.boundary;
+        let length = |boundary: Boundary, size: isize, from: isize, to: isize| match boundary {
+            Boundary::Wrap => measure_length(size, Interval::new(from, to)),
+            Boundary::Clamp => std::cmp::max(to - from + 1, 0),
+        };
+        let length = Coord3(
+            length(boundary.0, matrix.size.0, from.0, to.0),
+            length(boundary.1, matrix.size.1, from.1, to.1),
+            length(boundary.2, matrix.size.2, from.2, to.2),
+        );
+        Self {
+            matrix,
+            from,
+            length,
+            offset: Coord3(0, 0, 0),
+            remaining: length.map(|l| l as usize).merge(std::ops::Mul::mul),
+        }
+    }
+}
+
+impl<'m, Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    std::iter::Iterator for Scan3<'m, Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+    type Item = (Coord3<isize>, &'m Element);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let pos = self.matrix.normalize(self.from + self.offset);
+        self.offset.0 += 1;
+        if self.offset.0 == self.length.0 {
+            self.offset.0 = 0;
+            self.offset.1 += 1;
+            if self.offset.1 == self.length.1 {
+                self.offset.1 = 0;
+                self.offset.2 += 1;
+            }
+        }
+        Some((pos, &self.matrix[pos]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    std::iter::ExactSizeIterator for Scan3<'_, Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+}
+
+/// [`Matrix3`]中`z`固定的一层，见[`Matrix3::layer`]
+///
+/// 用二维的位置访问，`x`和`y`按[`Matrix3`]的[`Boundary`]变换。
+/// 只能用[`MatrixView::iter`]和[`MatrixView::area`]遍历；
+/// 基于访问器的工具（[`Iterator::positions`]、[`Iterator::histogram`]、各种扫描方式等）
+/// 需要先用[`MatrixView::to_matrix`]复制成[`Matrix`]。
+pub struct MatrixView<'m, Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+{
+    matrix: &'m Matrix3<Element, CHUNK_X, CHUNK_Y, CHUNK_Z>,
+    z: isize,
+}
+
+#[allow(dead_code)]
+impl<'m, Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    MatrixView<'m, Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+    /// 这一层的大小
+    #[inline]
+    pub fn size(&self) -> Coord<isize> {
+        self.matrix.size.xy()
+    }
+
+    #[inline]
+    pub fn z(&self) -> isize {
+        self.z
+    }
+
+    /// 按行遍历这一层
+    #[inline]
+    pub fn iter(&self) -> impl std::iter::Iterator<Item = (Coord<isize>, &'m Element)> {
+        self.area(Coord(0, 0) | (self.size() - Coord(1, 1)))
+    }
+
+    /// 按行遍历这一层中的`area`，`area`的处理方式见[`Scan3`]
+    pub fn area(
+        &self,
+        area: Coord<Interval<isize>>,
+    ) -> impl std::iter::Iterator<Item = (Coord<isize>, &'m Element)> {
+        let z = Interval::new(self.z, self.z);
+        self.matrix
+            .area(Coord3(area.0, area.1, z))
+            .map(|(pos, element)| (pos.xy(), element))
+    }
+
+    /// 复制成二维的[`Matrix`]
+    pub fn to_matrix<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+        &self,
+    ) -> Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+    where
+        Element: Clone + Default,
+    {
+        let size = self.size().map(|s| s as usize);
+        Matrix::with_ctor(&size, |opt_pos| {
+            opt_pos.map_or_else(Element::default, |pos| self[pos].clone())
+        })
+    }
+}
+
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize>
+    std::ops::Index<Coord<isize>> for MatrixView<'_, Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+{
+    type Output = Element;
+
+    #[inline]
+    fn index(&self, index: Coord<isize>) -> &Self::Output {
+        &self.matrix[Coord3::from_xy(index, self.z)]
+    }
+}
+
+impl<Element, const CHUNK_X: usize, const CHUNK_Y: usize, const CHUNK_Z: usize> std::fmt::Display
+    for MatrixView<'_, Element, CHUNK_X, CHUNK_Y, CHUNK_Z>
+where
+    Element: std::string::ToString,
+{
+    /// 和[`Matrix`]的格式相同，左上角是层号
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size = self.size();
+        let head = format!("{}{}", HEAD_MATRIX, self.z);
+        write_sheet(f, &head, size, Coord(0, 0) | (size - Coord(1, 1)), |pos| {
+            print_element(&self[pos])
+        })
+    }
+}
+
+#[cfg(test)]
+mod matrix3_tests {
+    use super::*;
+
+    const WRAP: Coord3<Boundary> = Coord3(Boundary::Wrap, Boundary::Wrap, Boundary::Wrap);
+
+    fn value(pos: Coord3<isize>) -> isize {
+        pos.0 + 10 * pos.1 + 100 * pos.2
+    }
+
+    fn numbered<const CX: usize, const CY: usize, const CZ: usize>(
+        boundary: Coord3<Boundary>,
+    ) -> Matrix3<isize, CX, CY, CZ> {
+        Matrix3::with_ctor(&Coord3(5, 4, 3), boundary, |opt_pos| {
+            opt_pos.map_or(-1, value)
+        })
+    }
+
+    fn check<const CX: usize, const CY: usize, const CZ: usize>() {
+        let mut matrix = numbered::<CX, CY, CZ>(WRAP);
+        let cells: Vec<_> = matrix.iter().map(|(pos, v)| (pos, *v)).collect();
+        assert_eq!(cells.len(), 60);
+        let mut expected = vec![];
+        for z in 0..3 {
+            for y in 0..4 {
+                for x in 0..5 {
+                    expected.push((Coord3(x, y, z), value(Coord3(x, y, z))));
+                }
+            }
+        }
+        assert_eq!(cells, expected);
+
+        assert_eq!(matrix[Coord3(-1, 4, 5)], value(Coord3(4, 0, 2)));
+        matrix[Coord3(6, -1, -1)] = -7;
+        assert_eq!(matrix[Coord3(1, 3, 2)], -7);
+
+        // 跨过边界的区间
+        let area: Vec<_> = matrix
+            .area(Coord3(4, 3, 2) | Coord3(0, 0, 0))
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(
+            area,
+            vec![
+                Coord3(4, 3, 2),
+                Coord3(0, 3, 2),
+                Coord3(4, 0, 2),
+                Coord3(0, 0, 2),
+                Coord3(4, 3, 0),
+                Coord3(0, 3, 0),
+                Coord3(4, 0, 0),
+                Coord3(0, 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matrix3() {
+        check::<1, 1, 1>();
+        check::<2, 2, 2>();
+        check::<2, 3, 1>();
+        check::<4, 1, 3>();
+        check::<8, 8, 8>();
+    }
+
+    #[test]
+    fn test_clamp() {
+        let boundary = Coord3(Boundary::Wrap, Boundary::Wrap, Boundary::Clamp);
+        let matrix = numbered::<2, 2, 2>(boundary);
+        assert_eq!(matrix[Coord3(0, 0, -3)], value(Coord3(0, 0, 0)));
+        assert_eq!(matrix[Coord3(-1, 0, 9)], value(Coord3(4, 0, 2)));
+        assert_eq!(matrix.area(Coord3(0, 0, 2) | Coord3(0, 0, 1)).count(), 0);
+        assert_eq!(matrix.area(Coord3(0, 0, -5) | Coord3(0, 0, 1)).len(), 2);
+        assert_eq!(matrix.layer(7).z(), 2);
+    }
+
+    #[test]
+    fn test_layer() {
+        let matrix = numbered::<2, 3, 2>(WRAP);
+        let layer = matrix.layer(-2);
+        assert_eq!(layer.z(), 1);
+        assert_eq!(layer[Coord(-1, -1)], value(Coord3(4, 3, 1)));
+        let flat = layer.to_matrix::<2, 2>();
+        assert_eq!(
+            layer.iter().collect::<Vec<_>>(),
+            flat.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            layer.to_string(),
+            "M1, 0 , 1 , 2 , 3 , 4 \n\
+             0 ,100,101,102,103,104\n\
+             1 ,110,111,112,113,114\n\
+             2 ,120,121,122,123,124\n\
+             3 ,130,131,132,133,134"
+        );
+        assert_eq!(
+            matrix.to_string().matches(LINE_DELIM).count(),
+            3 * 4 + 2 * 2
+        );
+
+        let area: Vec<_> = layer
+            .area(Coord(4, 3) | Coord(0, 0))
+            .map(|(pos, v)| (pos, *v))
+            .collect();
+        assert_eq!(
+            area,
+            vec![
+                (Coord(4, 3), 134),
+                (Coord(0, 3), 130),
+                (Coord(4, 0), 104),
+                (Coord(0, 0), 100),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_constructors() {
+        type M = Matrix3<u8, 2, 3, 2>;
+        let matrix = M::try_new(&Coord3(3, 4, 5), WRAP).unwrap();
+        assert_eq!(*matrix.size(), Coord3(3, 4, 5));
+        assert_eq!(
+            M::try_new(&Coord3(3, 0, 5), WRAP).err(),
+            Some(MatrixError::ZeroSize {
+                size: Coord3(3, 0, 5).into()
+            })
+        );
+        assert_eq!(
+            Matrix3::<u8, 2, 0, 2>::try_new(&Coord3(1, 1, 1), WRAP).err(),
+            Some(MatrixError::ZeroChunkSize {
+                chunk: Coord3(2, 0, 2).into()
+            })
+        );
+        assert_eq!(
+            M::try_new(&Coord3(1, usize::MAX, 1), WRAP).err(),
+            Some(MatrixError::SizeOverflow {
+                size: Coord3(1, usize::MAX, 1).into()
+            })
+        );
+        let huge = Coord3(1 << 22, 1 << 22, 1 << 22);
+        assert_eq!(
+            M::try_with_ctor(&huge, WRAP, |_| 0).err(),
+            Some(MatrixError::AllocationOverflow { size: huge.into() })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "matrix size must not be zero")]
+    fn test_zero_size_panics() {
+        Matrix3::<u8, 2, 2, 2>::new(&Coord3(3, 4, 0), WRAP);
+    }
+}
@@ -1,13 +1,42 @@
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::ops::Index;
 
-use super::{coord::Coord, interval::Interval};
+use serde::{Deserialize, Serialize};
+
+use crate::rng::Pcg32;
+use crate::sword::SWord;
+
+use super::{
+    coord::Coord, coord3::Coord3, hex::Axial, interval::Interval, path::Neighborhood, torus::Torus,
+};
 
 include!("matrix.rs");
+include!("error.rs");
+include!("patch.rs");
+include!("map.rs");
+include!("fill.rs");
 
 include!("access.rs");
 include!("iter.rs");
+include!("iter_mut.rs");
+include!("query.rs");
 
 include!("scan.rs");
+include!("column_scan.rs");
+include!("serpentine.rs");
+include!("rings.rs");
+include!("hex.rs");
+include!("shuffled.rs");
+include!("combine.rs");
+include!("shape.rs");
+
+include!("tracked.rs");
+include!("journaled.rs");
+include!("layers.rs");
+include!("bit_matrix.rs");
+include!("dyn_matrix.rs");
+include!("matrix3.rs");
 
 include!("util.rs");
 include!("fmt.rs");
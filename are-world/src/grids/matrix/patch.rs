@@ -0,0 +1,319 @@
+/// 两个同样大小的矩阵之间的差别，见[`Matrix::diff`]
+///
+/// 变化的格子按块分组，块内同一行中连续的格子合并成一段[`Run`]。
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Patch<Element> {
+    size: Coord<isize>,
+    chunks: Vec<ChunkPatch<Element>>,
+}
+
+/// 一个块中变化的格子
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct ChunkPatch<Element> {
+    /// 块在所有块中的位置
+    pub chunk: Coord<isize>,
+    pub runs: Vec<Run<Element>>,
+}
+
+/// 同一行中连续变化的格子
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Run<Element> {
+    /// 第一个格子的位置
+    pub start: Coord<isize>,
+    /// 从`start`开始向右每个格子的新值
+    pub new: Vec<Element>,
+    /// 对应的旧值，不保存时是[`None`]
+    pub old: Option<Vec<Element>>,
+}
+
+#[allow(dead_code)]
+impl<Element> Patch<Element> {
+    /// 补丁适用的矩阵大小
+    #[inline]
+    pub fn size(&self) -> Coord<isize> {
+        self.size
+    }
+
+    #[inline]
+    pub fn chunks(&self) -> &[ChunkPatch<Element>] {
+        &self.chunks
+    }
+
+    /// 变化的格子数
+    pub fn len(&self) -> usize {
+        self.runs().map(|run| run.new.len()).sum()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// 每一段都保存了旧值
+    pub fn has_old(&self) -> bool {
+        self.runs().all(|run| run.old.is_some())
+    }
+
+    /// 变化的格子的位置和新值
+    pub fn cells(&self) -> impl std::iter::Iterator<Item = (Coord<isize>, &Element)> {
+        self.runs().flat_map(|run| {
+            run.new
+                .iter()
+                .enumerate()
+                .map(move |(i, value)| (run.start + Coord(i as isize, 0), value))
+        })
+    }
+
+    /// 去掉旧值，只保留[`apply`](Matrix::apply)需要的部分
+    pub fn without_old(mut self) -> Self {
+        for run in self.runs_mut() {
+            run.old = None;
+        }
+        self
+    }
+
+    /// 交换新值和旧值，得到撤销这个补丁的补丁。
+    /// 有一段没有保存旧值时返回[`None`]
+    pub fn invert(mut self) -> Option<Self> {
+        if !self.has_old() {
+            return None;
+        }
+        for run in self.runs_mut() {
+            let old = run.old.take().unwrap();
+            run.old = Some(std::mem::replace(&mut run.new, old));
+        }
+        Some(self)
+    }
+
+    #[inline]
+    fn empty(size: Coord<isize>) -> Self {
+        Self {
+            size,
+            chunks: vec![],
+        }
+    }
+
+    /// 在末尾加上块`chunk`中的一个格子，能接上最后一段时合并。
+    /// 必须按格子在矩阵中的偏移量从小到大加入
+    fn push(&mut self, chunk: Coord<isize>, pos: Coord<isize>, new: Element, old: Element) {
+        if let Some(last) = self.chunks.last_mut().filter(|last| last.chunk == chunk) {
+            let run = last.runs.last_mut().unwrap();
+            if run.start + Coord(run.new.len() as isize, 0) == pos {
+                run.new.push(new);
+                run.old.as_mut().unwrap().push(old);
+            } else {
+                last.runs.push(Run::single(pos, new, old));
+            }
+        } else {
+            self.chunks.push(ChunkPatch {
+                chunk,
+                runs: vec![Run::single(pos, new, old)],
+            });
+        }
+    }
+
+    fn runs(&self) -> impl std::iter::Iterator<Item = &Run<Element>> {
+        self.chunks.iter().flat_map(|chunk| chunk.runs.iter())
+    }
+
+    fn runs_mut(&mut self) -> impl std::iter::Iterator<Item = &mut Run<Element>> {
+        self.chunks
+            .iter_mut()
+            .flat_map(|chunk| chunk.runs.iter_mut())
+    }
+}
+
+impl<Element> Run<Element> {
+    #[inline]
+    fn single(start: Coord<isize>, new: Element, old: Element) -> Self {
+        Self {
+            start,
+            new: vec![new],
+            old: Some(vec![old]),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Element: Clone + PartialEq,
+{
+    /// 找出从`self`变成`other`需要修改的格子，补丁中同时保存旧值。
+    ///
+    /// 大小不同时返回[`MatrixError::SizeMismatch`]
+    pub fn diff(&self, other: &Self) -> Result<Patch<Element>, MatrixError> {
+        check_size(self.size, other.size)?;
+        let size = self.size;
+        let chunk_size = Self::calc_chunk_size(Coord(size.0 as usize, size.1 as usize));
+        let mut patch = Patch::empty(size);
+        for chunk_y in 0..chunk_size.1 as isize {
+            for chunk_x in 0..chunk_size.0 as isize {
+                let from = Coord(
+                    chunk_x * CHUNK_WIDTH as isize,
+                    chunk_y * CHUNK_HEIGHT as isize,
+                );
+                let to = Coord(
+                    std::cmp::min(from.0 + CHUNK_WIDTH as isize, size.0),
+                    std::cmp::min(from.1 + CHUNK_HEIGHT as isize, size.1),
+                );
+                for y in from.1..to.1 {
+                    for x in from.0..to.0 {
+                        let pos = Coord(x, y);
+                        // SAFETY: `pos`在矩阵之内，两个矩阵大小相同
+                        let (old, new) = unsafe {
+                            let addr = Self::calc_address_unchecked(size, pos);
+                            (self.get_by_addr(addr), other.get_by_addr(addr))
+                        };
+                        if old != new {
+                            patch.push(Coord(chunk_x, chunk_y), pos, new.clone(), old.clone());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(patch)
+    }
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Element: Clone,
+{
+    /// 把补丁中的新值写入矩阵。
+    ///
+    /// 补丁的大小和矩阵不同时返回[`MatrixError::SizeMismatch`]，矩阵不变
+    pub fn apply(&mut self, patch: &Patch<Element>) -> Result<(), MatrixError> {
+        check_size(self.size, patch.size)?;
+        for (pos, value) in patch.cells() {
+            self[pos] = value.clone();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod patch_tests {
+    use super::*;
+
+    fn pair<const CW: usize, const CH: usize>() -> (Matrix<i32, CW, CH>, Matrix<i32, CW, CH>) {
+        let before = Matrix::<i32, CW, CH>::with_ctor(&Coord(7, 5), |opt_pos| {
+            opt_pos.map_or(-1, |pos| (pos.0 + 10 * pos.1) as i32)
+        });
+        let mut after = before.clone();
+        for pos in [
+            Coord(1, 0),
+            Coord(2, 0),
+            Coord(3, 0),
+            Coord(5, 0),
+            Coord(6, 4),
+            Coord(0, 2),
+            Coord(1, 2),
+        ] {
+            after[pos] = 100;
+        }
+        (before, after)
+    }
+
+    fn assert_same<const CW: usize, const CH: usize>(
+        a: &Matrix<i32, CW, CH>,
+        b: &Matrix<i32, CW, CH>,
+    ) {
+        assert!(a.iter().all(|(pos, value)| b[pos] == *value));
+    }
+
+    fn test_sub<const CW: usize, const CH: usize>() {
+        let (before, after) = pair::<CW, CH>();
+        let patch = before.diff(&after).unwrap();
+        assert_eq!(patch.len(), 7);
+        assert!(patch.has_old());
+        for (pos, value) in patch.cells() {
+            assert_eq!(*value, 100);
+            assert_ne!(before[pos], 100);
+        }
+        for chunk in patch.chunks() {
+            for run in &chunk.runs {
+                // 每一段都在同一个块的同一行中
+                let last = run.start + Coord(run.new.len() as isize - 1, 0);
+                assert_eq!(run.start.0 / CW as isize, chunk.chunk.0);
+                assert_eq!(last.0 / CW as isize, chunk.chunk.0);
+                assert_eq!(run.start.1 / CH as isize, chunk.chunk.1);
+            }
+        }
+
+        let mut matrix = before.clone();
+        matrix.apply(&patch).unwrap();
+        assert_same(&matrix, &after);
+        matrix.apply(&patch.clone().invert().unwrap()).unwrap();
+        assert_same(&matrix, &before);
+
+        let stripped = patch.clone().without_old();
+        assert!(!stripped.has_old());
+        assert_eq!(stripped.clone().invert(), None);
+        let mut matrix = before.clone();
+        matrix.apply(&stripped).unwrap();
+        assert_same(&matrix, &after);
+
+        assert!(before.diff(&before).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_and_apply() {
+        test_sub::<1, 1>();
+        test_sub::<2, 3>();
+        test_sub::<4, 4>();
+        test_sub::<8, 8>();
+
+        let (_, after) = pair::<1, 1>();
+        let patch = pair::<1, 1>().0.diff(&after).unwrap();
+        assert_eq!(patch.chunks().len(), 7);
+        let (_, after) = pair::<8, 8>();
+        let patch = pair::<8, 8>().0.diff(&after).unwrap();
+        assert_eq!(patch.chunks().len(), 1);
+        assert_eq!(patch.chunks()[0].runs.len(), 4);
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let small = Matrix::<i32, 2, 2>::new(&Coord(3, 3));
+        let mut large = Matrix::<i32, 2, 2>::new(&Coord(4, 3));
+        let error = small.diff(&large).unwrap_err();
+        assert_eq!(
+            error,
+            MatrixError::SizeMismatch {
+                expected: Coord(3, 3),
+                found: Coord(4, 3)
+            }
+        );
+        assert!(error.to_string().contains("size mismatch"));
+
+        let patch = small.diff(&small).unwrap();
+        assert!(large.apply(&patch).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let (before, after) = pair::<2, 3>();
+        let patch = before.diff(&after).unwrap();
+        let text = serde_json::to_string(&patch).unwrap();
+        let loaded: Patch<i32> = serde_json::from_str(&text).unwrap();
+        assert_eq!(loaded, patch);
+
+        let mut matrix = before.clone();
+        matrix.apply(&loaded).unwrap();
+        assert_same(&matrix, &after);
+        matrix.apply(&loaded.invert().unwrap()).unwrap();
+        assert_same(&matrix, &before);
+
+        let stripped = patch.without_old();
+        let text = serde_json::to_string(&stripped).unwrap();
+        let loaded: Patch<i32> = serde_json::from_str(&text).unwrap();
+        assert_eq!(loaded, stripped);
+        let mut matrix = before.clone();
+        matrix.apply(&loaded).unwrap();
+        assert_same(&matrix, &after);
+    }
+}
@@ -0,0 +1,293 @@
+#[allow(dead_code)]
+impl<'m, Element, Access, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Iterator<'m, Element, Access, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Access: Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>,
+{
+    /// `predicate(pos, element)`为`true`的格子的位置，按访问顺序排列
+    pub fn positions(
+        self,
+        mut predicate: impl FnMut(Coord<isize>, &Element) -> bool + 'm,
+    ) -> impl std::iter::Iterator<Item = Coord<isize>> + 'm
+    where
+        Access: 'm,
+    {
+        self.filter_map(move |(pos, element)| predicate(pos, element).then_some(pos))
+    }
+
+    /// 按访问顺序第一个`predicate(pos, element)`为`true`的格子
+    #[inline]
+    pub fn find_first(
+        mut self,
+        mut predicate: impl FnMut(Coord<isize>, &Element) -> bool,
+    ) -> Option<(Coord<isize>, &'m Element)> {
+        self.find(|(pos, element)| predicate(*pos, element))
+    }
+
+    /// `predicate(pos, element)`为`true`的格子数
+    #[inline]
+    pub fn count_where(self, mut predicate: impl FnMut(Coord<isize>, &Element) -> bool) -> usize {
+        self.filter(|(pos, element)| predicate(*pos, element))
+            .count()
+    }
+
+    /// 每种值出现的次数
+    pub fn histogram(self) -> HashMap<&'m Element, usize>
+    where
+        Element: Hash + Eq,
+    {
+        let mut histogram = HashMap::new();
+        for (_, element) in self {
+            *histogram.entry(element).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// `key(element)`最小的格子的位置，有多个时取访问顺序中第一个
+    pub fn position_min_by_key<K: Ord>(
+        self,
+        mut key: impl FnMut(&Element) -> K,
+    ) -> Option<Coord<isize>> {
+        let mut best: Option<(K, Coord<isize>)> = None;
+        for (pos, element) in self {
+            let k = key(element);
+            if best.as_ref().is_none_or(|(b, _)| k < *b) {
+                best = Some((k, pos));
+            }
+        }
+        best.map(|(_, pos)| pos)
+    }
+
+    /// `key(element)`最大的格子的位置，有多个时取访问顺序中第一个
+    /// （[`std::iter::Iterator::max_by_key`]取的是最后一个）
+    pub fn position_max_by_key<K: Ord>(
+        self,
+        mut key: impl FnMut(&Element) -> K,
+    ) -> Option<Coord<isize>> {
+        self.position_min_by_key(move |element| std::cmp::Reverse(key(element)))
+    }
+
+    /// 每种值和包含这种值所有格子的最小区域，按第一次出现的顺序排列
+    pub fn group_by(self) -> Vec<(&'m Element, Coord<Interval<isize>>)>
+    where
+        Element: Hash + Eq,
+    {
+        let size = self.accessor.matrix_size();
+        let mut index: HashMap<&Element, usize> = HashMap::new();
+        // 每种值出现过的列和行
+        let mut groups: Vec<(&'m Element, Vec<isize>, Vec<isize>)> = vec![];
+        for (pos, element) in self {
+            let i = *index.entry(element).or_insert_with(|| {
+                groups.push((element, vec![], vec![]));
+                groups.len() - 1
+            });
+            groups[i].1.push(pos.0);
+            groups[i].2.push(pos.1);
+        }
+        groups
+            .into_iter()
+            .map(|(element, columns, rows)| {
+                let area = Coord(
+                    Self::bounding(columns, size.0),
+                    Self::bounding(rows, size.1),
+                );
+                (element, area)
+            })
+            .collect()
+    }
+
+    /// 环上包含`occupied`中所有点的最短的一个区间，`occupied`不能为空
+    ///
+    /// 去掉相邻两点之间最长的一段空隙得到，同[`IntervalSet::bounding`](super::interval_set::IntervalSet::bounding)。
+    fn bounding(mut occupied: Vec<isize>, modulus: isize) -> Interval<isize> {
+        occupied.sort_unstable();
+        occupied.dedup();
+        let first = occupied[0];
+        let last = occupied[occupied.len() - 1];
+        // 空隙用两端的点表示，先看跨过边界的一段
+        let mut longest = (last, first, modulus - last + first);
+        for pair in occupied.windows(2) {
+            if pair[1] - pair[0] > longest.2 {
+                longest = (pair[0], pair[1], pair[1] - pair[0]);
+            }
+        }
+        if longest.2 <= 1 {
+            return Interval::new(0, modulus - 1);
+        }
+        Interval::new(longest.1, longest.0)
+    }
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 见[`Iterator::positions`]，按行遍历整个矩阵
+    #[inline]
+    pub fn positions<'m>(
+        &'m self,
+        predicate: impl FnMut(Coord<isize>, &Element) -> bool + 'm,
+    ) -> impl std::iter::Iterator<Item = Coord<isize>> + 'm {
+        self.iter().positions(predicate)
+    }
+
+    /// 见[`Iterator::find_first`]，按行遍历整个矩阵
+    #[inline]
+    pub fn find_first(
+        &self,
+        predicate: impl FnMut(Coord<isize>, &Element) -> bool,
+    ) -> Option<(Coord<isize>, &Element)> {
+        self.iter().find_first(predicate)
+    }
+
+    /// 见[`Iterator::count_where`]
+    #[inline]
+    pub fn count_where(&self, predicate: impl FnMut(Coord<isize>, &Element) -> bool) -> usize {
+        self.iter().count_where(predicate)
+    }
+
+    /// 见[`Iterator::histogram`]
+    #[inline]
+    pub fn histogram(&self) -> HashMap<&Element, usize>
+    where
+        Element: Hash + Eq,
+    {
+        self.iter().histogram()
+    }
+
+    /// 见[`Iterator::position_min_by_key`]，按行遍历整个矩阵
+    #[inline]
+    pub fn position_min_by_key<K: Ord>(
+        &self,
+        key: impl FnMut(&Element) -> K,
+    ) -> Option<Coord<isize>> {
+        self.iter().position_min_by_key(key)
+    }
+
+    /// 见[`Iterator::position_max_by_key`]，按行遍历整个矩阵
+    #[inline]
+    pub fn position_max_by_key<K: Ord>(
+        &self,
+        key: impl FnMut(&Element) -> K,
+    ) -> Option<Coord<isize>> {
+        self.iter().position_max_by_key(key)
+    }
+
+    /// 见[`Iterator::group_by`]，按行遍历整个矩阵
+    #[inline]
+    pub fn group_by(&self) -> Vec<(&Element, Coord<Interval<isize>>)>
+    where
+        Element: Hash + Eq,
+    {
+        self.iter().group_by()
+    }
+}
+
+#[cfg(test)]
+mod query_tests {
+    use super::*;
+
+    type M = Matrix<char, 3, 2>;
+
+    /// ```text
+    /// a b b a a
+    /// c c a a b
+    /// c b b c a
+    /// a a c b a
+    /// ```
+    fn matrix() -> M {
+        let rows = ["abbaa", "ccaab", "cbbca", "aacba"];
+        M::with_ctor(&Coord(5, 4), |opt_pos| {
+            opt_pos.map_or(' ', |pos| {
+                rows[pos.1 as usize].as_bytes()[pos.0 as usize] as char
+            })
+        })
+    }
+
+    #[test]
+    fn test_find_and_count() {
+        let matrix = matrix();
+        let wrapped = Coord(3, 3) | Coord(1, 1);
+        assert_eq!(
+            matrix
+                .area(wrapped)
+                .positions(|_, c| *c == 'c')
+                .collect::<Vec<_>>(),
+            vec![Coord(0, 1), Coord(1, 1)]
+        );
+        assert_eq!(
+            matrix.area(wrapped).find_first(|_, c| *c == 'b'),
+            Some((Coord(3, 3), &'b'))
+        );
+        assert_eq!(
+            matrix.find_first(|_, c| *c == 'b'),
+            Some((Coord(1, 0), &'b'))
+        );
+        assert_eq!(matrix.find_first(|_, c| *c == 'z'), None);
+        assert_eq!(matrix.area(wrapped).count_where(|_, c| *c == 'a'), 7);
+        assert_eq!(matrix.count_where(|pos, _| pos.0 == pos.1), 4);
+        assert_eq!(matrix.positions(|_, c| *c == 'c').count(), 5);
+
+        let column = matrix.column_scan(Coord(1, 0) | Coord(2, 3));
+        assert_eq!(
+            column.find_first(|_, c| *c == 'c'),
+            Some((Coord(1, 1), &'c'))
+        );
+    }
+
+    #[test]
+    fn test_histogram_and_extremes() {
+        let matrix = matrix();
+        let histogram = matrix.histogram();
+        assert_eq!(histogram[&'a'], 9);
+        assert_eq!(histogram[&'b'], 6);
+        assert_eq!(histogram[&'c'], 5);
+        let wrapped = matrix.area(Coord(4, 3) | Coord(0, 0)).histogram();
+        assert_eq!(wrapped.len(), 1);
+        assert_eq!(wrapped[&'a'], 4);
+
+        assert_eq!(matrix.position_min_by_key(|c| *c), Some(Coord(0, 0)));
+        assert_eq!(matrix.position_max_by_key(|c| *c), Some(Coord(0, 1)));
+        assert_eq!(
+            matrix
+                .area(Coord(3, 2) | Coord(1, 2))
+                .position_max_by_key(|c| *c),
+            Some(Coord(3, 2))
+        );
+        assert_eq!(
+            matrix
+                .serpentine(Coord(0, 0) | Coord(4, 1))
+                .position_min_by_key(|c| *c == 'a'),
+            Some(Coord(1, 0))
+        );
+    }
+
+    #[test]
+    fn test_group_by() {
+        let matrix = matrix();
+        let groups = matrix.group_by();
+        assert_eq!(
+            groups,
+            vec![
+                (&'a', Coord(0, 0) | Coord(4, 3)),
+                (&'b', Coord(1, 0) | Coord(4, 3)),
+                (&'c', Coord(0, 1) | Coord(3, 3)),
+            ]
+        );
+        let groups = matrix.area(Coord(3, 3) | Coord(0, 0)).group_by();
+        assert_eq!(
+            groups,
+            vec![
+                (&'b', Coord(3, 3) | Coord(3, 3)),
+                (&'a', Coord(3, 3) | Coord(0, 0))
+            ]
+        );
+        for (value, area) in matrix.group_by() {
+            for (pos, c) in matrix.iter() {
+                if c == value {
+                    assert!(area.contains(&pos));
+                }
+            }
+        }
+    }
+}
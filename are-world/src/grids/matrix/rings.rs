@@ -0,0 +1,300 @@
+/// 从中心一圈一圈向外访问的访问器，见[`Matrix::rings`]
+///
+/// 每个格子只访问一次，所在的圈数等于它在环面上到中心的步数。
+/// 同一圈内，[`Neighborhood::Eight`]从左上角开始顺时针访问，
+/// [`Neighborhood::Four`]从正上方开始顺时针访问。
+#[derive(Clone)]
+pub struct Rings<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    center: Coord<isize>,
+    neighborhood: Neighborhood,
+    max_radius: isize,
+
+    radius: isize,
+    index: isize,
+    length: usize,
+
+    torus: Torus,
+}
+
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Rings<CHUNK_WIDTH, CHUNK_HEIGHT> {
+    /// `center`必须已经标准化
+    fn new(
+        matrix_size: Coord<isize>,
+        center: Coord<isize>,
+        max_radius: usize,
+        neighborhood: Neighborhood,
+    ) -> Self {
+        let half = matrix_size.map(|s| s / 2);
+        let limit = match neighborhood {
+            Neighborhood::Four => half.0 + half.1,
+            Neighborhood::Eight => std::cmp::max(half.0, half.1),
+        };
+        let max_radius = std::cmp::min(max_radius.try_into().unwrap_or(isize::MAX), limit);
+
+        // 每个维度上离中心不超过`k`的格子数
+        let within = |size: isize, k: isize| std::cmp::min(size, 2 * k + 1);
+        let length = match neighborhood {
+            Neighborhood::Four => (-(matrix_size.0 - 1) / 2..=half.0)
+                .filter(|dx| dx.abs() <= max_radius)
+                .map(|dx| within(matrix_size.1, max_radius - dx.abs()))
+                .sum(),
+            Neighborhood::Eight => {
+                within(matrix_size.0, max_radius) * within(matrix_size.1, max_radius)
+            }
+        };
+
+        Self {
+            center,
+            neighborhood,
+            max_radius,
+            radius: 0,
+            index: 0,
+            length: length as usize,
+            torus: Torus::new(matrix_size),
+        }
+    }
+
+    /// 第`radius`圈的第`index`个偏移量，圈上没有这么多格子时返回[`None`]
+    fn ring_offset(
+        neighborhood: Neighborhood,
+        radius: isize,
+        index: isize,
+    ) -> Option<Coord<isize>> {
+        if radius == 0 {
+            return if index == 0 { Some(Coord(0, 0)) } else { None };
+        }
+        let (side_len, sides) = match neighborhood {
+            Neighborhood::Four => (
+                radius,
+                [
+                    Coord(0, -radius),
+                    Coord(radius, 0),
+                    Coord(0, radius),
+                    Coord(-radius, 0),
+                ],
+            ),
+            Neighborhood::Eight => (
+                2 * radius,
+                [
+                    Coord(-radius, -radius),
+                    Coord(radius, -radius),
+                    Coord(radius, radius),
+                    Coord(-radius, radius),
+                ],
+            ),
+        };
+        let steps = match neighborhood {
+            Neighborhood::Four => [Coord(1, 1), Coord(-1, 1), Coord(-1, -1), Coord(1, -1)],
+            Neighborhood::Eight => [Coord(1, 0), Coord(0, 1), Coord(-1, 0), Coord(0, -1)],
+        };
+        let side = (index / side_len) as usize;
+        let k = index % side_len;
+        Some(*sides.get(side)? + steps[side] * Coord(k, k))
+    }
+
+    /// `offset`是否是[`Torus::displacement`]会给出的位移，
+    /// 同一个格子只有一个这样的偏移量
+    #[inline]
+    fn is_canonical(&self, offset: Coord<isize>) -> bool {
+        let size = self.torus.size();
+        -(size.0 - 1) / 2 <= offset.0
+            && offset.0 <= size.0 / 2
+            && -(size.1 - 1) / 2 <= offset.1
+            && offset.1 <= size.1 / 2
+    }
+}
+
+// SAFETY: 位置都经过标准化，偏移量由标准化以后的位置计算
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>
+    for Rings<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        while self.length > 0 && self.radius <= self.max_radius {
+            let offset = Self::ring_offset(self.neighborhood, self.radius, self.index);
+            let offset = match offset {
+                Some(offset) => offset,
+                None => {
+                    self.radius += 1;
+                    self.index = 0;
+                    continue;
+                }
+            };
+            self.index += 1;
+            if self.is_canonical(offset) {
+                self.length -= 1;
+                let pos = self.torus.normalize(self.center + offset);
+                let addr =
+                    Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address(self.torus.size(), pos);
+                return Some((pos, addr));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        let size = self.torus.size();
+        let reach = size.map(|s| {
+            if 2 * self.max_radius + 1 >= s {
+                Interval::new(-(s - 1) / 2, s / 2)
+            } else {
+                Interval::new(-self.max_radius, self.max_radius)
+            }
+        });
+        let from = self
+            .torus
+            .normalize(self.center + Coord(reach.0.from, reach.1.from));
+        let to = self
+            .torus
+            .normalize(self.center + Coord(reach.0.to, reach.1.to));
+        from | to
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.neighborhood.steps(self.torus.size(), self.center, pos) <= self.max_radius as u64
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MRings"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.torus.size()
+    }
+}
+
+#[cfg(test)]
+mod rings_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_ring_order() {
+        let matrix = Matrix::<(), 2, 2>::new(&Coord(9, 9));
+        let visited: Vec<_> = matrix
+            .rings(Coord(4, 4), 1, Neighborhood::Eight)
+            .map(|(pos, _)| pos - Coord(4, 4))
+            .collect();
+        assert_eq!(
+            visited,
+            vec![
+                Coord(0, 0),
+                Coord(-1, -1),
+                Coord(0, -1),
+                Coord(1, -1),
+                Coord(1, 0),
+                Coord(1, 1),
+                Coord(0, 1),
+                Coord(-1, 1),
+                Coord(-1, 0),
+            ]
+        );
+        let visited: Vec<_> = matrix
+            .rings(Coord(0, 0), 2, Neighborhood::Four)
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(
+            visited,
+            vec![
+                Coord(0, 0),
+                Coord(0, 8),
+                Coord(1, 0),
+                Coord(0, 1),
+                Coord(8, 0),
+                Coord(0, 7),
+                Coord(1, 8),
+                Coord(2, 0),
+                Coord(1, 1),
+                Coord(0, 2),
+                Coord(8, 1),
+                Coord(7, 0),
+                Coord(8, 8),
+            ]
+        );
+    }
+
+    fn test_sub<const CW: usize, const CH: usize>() {
+        for w in [1, 2, 3, 4, 5, 8] {
+            for h in [1, 2, 3, 6, 7] {
+                let matrix = Matrix::<String, CW, CH>::with_ctor(&Coord(w, h), |opt_pos| {
+                    opt_pos.map_or(String::new(), |pos| pos.to_string())
+                });
+                let torus = matrix.torus();
+                for n in [Neighborhood::Four, Neighborhood::Eight] {
+                    for center in [Coord(0, 0), Coord(w as isize - 1, h as isize / 2)] {
+                        for max_radius in [0, 1, 2, 3, 100] {
+                            let mut iter = matrix.rings(center, max_radius, n);
+                            let total = iter.len();
+                            let mut seen = HashSet::new();
+                            let mut last = 0;
+                            while let Some((pos, value)) = iter.next() {
+                                assert_eq!(*value, pos.to_string());
+                                let steps = n.steps(*matrix.size(), center, pos);
+                                assert!(steps >= last);
+                                assert!(steps <= max_radius as u64);
+                                assert!(iter.accessor.contains(pos));
+                                last = steps;
+                                assert!(seen.insert(pos), "{} visited twice", pos);
+                                assert_eq!(iter.len(), total - seen.len());
+                            }
+                            for (pos, _) in matrix.iter() {
+                                let inside =
+                                    n.steps(*matrix.size(), center, pos) <= max_radius as u64;
+                                assert_eq!(seen.contains(&pos), inside);
+                                assert_eq!(torus.normalize(pos), pos);
+                            }
+                            assert_eq!(total, seen.len());
+                            let area = matrix.rings(center, max_radius, n).accessor.super_area();
+                            for pos in &seen {
+                                assert!(area.contains(pos));
+                            }
+                        }
+                    }
+                }
+                let spiral: HashSet<_> = matrix
+                    .spiral(Coord(-1, -1), Neighborhood::Four)
+                    .map(|(pos, _)| pos)
+                    .collect();
+                assert_eq!(spiral.len(), w * h);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rings_cover_torus() {
+        test_sub::<1, 1>();
+        test_sub::<2, 3>();
+    }
+
+    #[test]
+    fn test_find_nearest() {
+        let matrix = Matrix::<u32, 3, 2>::with_ctor(&Coord(10, 8), |opt_pos| match opt_pos {
+            Some(Coord(8, 7)) | Some(Coord(3, 3)) => 1,
+            _ => 0,
+        });
+        let found = matrix.find_nearest(Coord(0, 0), Neighborhood::Four, |_, e| *e == 1);
+        assert_eq!(found, Some((Coord(8, 7), &1)));
+        let found = matrix.find_nearest(Coord(2, 2), Neighborhood::Eight, |_, e| *e == 1);
+        assert_eq!(found, Some((Coord(3, 3), &1)));
+        assert_eq!(
+            matrix.find_nearest(Coord(2, 2), Neighborhood::Eight, |_, e| *e == 2),
+            None
+        );
+
+        // 找到以后不再继续访问
+        let mut visited = 0;
+        matrix.find_nearest(Coord(3, 2), Neighborhood::Eight, |_, e| {
+            visited += 1;
+            *e == 1
+        });
+        assert_eq!(visited, 1 + 6);
+
+        let text = matrix.rings(Coord(0, 0), 1, Neighborhood::Four).to_string();
+        assert!(text.starts_with("MRings"));
+    }
+}
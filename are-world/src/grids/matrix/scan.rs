@@ -1,6 +1,8 @@
 // by *StarvinCulex @2021/11/21*
+#[derive(Clone)]
 pub struct Scan<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
     at: Coord<isize>,
+    back: Coord<isize>,
     length: usize,
 
     area: Coord<Interval<isize>>,
@@ -16,6 +18,7 @@ impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Scan<CHUNK_WIDTH, CHUN
     fn new(matrix_size: Coord<isize>, area: Coord<Interval<isize>>) -> Self {
         let mut instance = Self {
             at: area.from(),
+            back: area.to(),
             length: 0,
             area,
             matrix_size,
@@ -45,7 +48,8 @@ impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Scan<CHUNK_WIDTH, CHUN
     }
 }
 
-impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>
+// SAFETY: `area`已经标准化，遍历时的位置都在矩阵之内
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>
     for Scan<CHUNK_WIDTH, CHUNK_HEIGHT>
 {
     #[inline]
@@ -65,19 +69,9 @@ impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH,
             self.at = {
                 let next_line = at.0 == self.area.0.to;
                 if next_line {
-                    let y = if at.1 + 1 == self.matrix_size.1 {
-                        0
-                    } else {
-                        at.1 + 1
-                    };
-                    Coord(self.area.0.from, y)
+                    Coord(self.area.0.from, wrapping_inc(at.1, self.matrix_size.1))
                 } else {
-                    let x = if at.0 + 1 == self.matrix_size.0 {
-                        0
-                    } else {
-                        at.0 + 1
-                    };
-                    Coord(x, at.1)
+                    Coord(wrapping_inc(at.0, self.matrix_size.0), at.1)
                 }
             };
 
@@ -101,4 +95,60 @@ impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH,
     fn r#type(&self) -> &'static str {
         "MScan"
     }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.matrix_size
+    }
+}
+
+// SAFETY: 同`Accessor`
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    DoubleEndedAccessor<CHUNK_WIDTH, CHUNK_HEIGHT> for Scan<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<(Coord<isize>, usize)> {
+        if self.len() == 0 {
+            None
+        } else {
+            let at = self.back;
+            let addr = unsafe {
+                Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address_unchecked(
+                    self.matrix_size,
+                    at,
+                )
+            };
+
+            self.length -= 1;
+            self.back = {
+                let prev_line = at.0 == self.area.0.from;
+                if prev_line {
+                    Coord(self.area.0.to, wrapping_dec(at.1, self.matrix_size.1))
+                } else {
+                    Coord(wrapping_dec(at.0, self.matrix_size.0), at.1)
+                }
+            };
+
+            Some((at, addr))
+        }
+    }
+}
+
+/// 在`0..size`的环上前进一格
+#[inline]
+const fn wrapping_inc(value: isize, size: isize) -> isize {
+    if value + 1 == size {
+        0
+    } else {
+        value + 1
+    }
+}
+
+/// 在`0..size`的环上后退一格
+#[inline]
+const fn wrapping_dec(value: isize, size: isize) -> isize {
+    if value == 0 {
+        size - 1
+    } else {
+        value - 1
+    }
 }
@@ -0,0 +1,226 @@
+/// 蛇形遍历区域的访问器，见[`Matrix::serpentine`]
+#[derive(Clone)]
+pub struct Serpentine<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    at: Coord<isize>,
+    /// `at`所在的行是否从左到右
+    at_rightward: bool,
+    back: Coord<isize>,
+    /// `back`所在的行是否从左到右
+    back_rightward: bool,
+    length: usize,
+
+    area: Coord<Interval<isize>>,
+
+    matrix_size: Coord<isize>,
+}
+
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Serpentine<CHUNK_WIDTH, CHUNK_HEIGHT> {
+    /// `area`必须已经标准化
+    #[inline]
+    fn new(matrix_size: Coord<isize>, area: Coord<Interval<isize>>) -> Self {
+        let size = measure_area(matrix_size, area);
+        let back_rightward = size.1 % 2 == 1;
+        let back = if back_rightward {
+            area.to()
+        } else {
+            Coord(area.0.from, area.1.to)
+        };
+        Self {
+            at: area.from(),
+            at_rightward: true,
+            back,
+            back_rightward,
+            length: size.merge(std::ops::Mul::mul) as usize,
+            area,
+            matrix_size,
+        }
+    }
+}
+
+// SAFETY: `area`已经标准化，遍历时的位置都在矩阵之内
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>
+    for Serpentine<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    #[inline]
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        if self.length == 0 {
+            return None;
+        }
+        let at = self.at;
+        let addr = unsafe {
+            Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address_unchecked(self.matrix_size, at)
+        };
+        self.length -= 1;
+        let line_end = if self.at_rightward {
+            self.area.0.to
+        } else {
+            self.area.0.from
+        };
+        self.at = if at.0 == line_end {
+            self.at_rightward = !self.at_rightward;
+            Coord(at.0, wrapping_inc(at.1, self.matrix_size.1))
+        } else if self.at_rightward {
+            Coord(wrapping_inc(at.0, self.matrix_size.0), at.1)
+        } else {
+            Coord(wrapping_dec(at.0, self.matrix_size.0), at.1)
+        };
+        Some((at, addr))
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        self.area
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.area.contains(&pos)
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MSerpentine"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.matrix_size
+    }
+}
+
+// SAFETY: 同`Accessor`
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    DoubleEndedAccessor<CHUNK_WIDTH, CHUNK_HEIGHT> for Serpentine<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<(Coord<isize>, usize)> {
+        if self.length == 0 {
+            return None;
+        }
+        let at = self.back;
+        let addr = unsafe {
+            Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address_unchecked(self.matrix_size, at)
+        };
+        self.length -= 1;
+        let line_start = if self.back_rightward {
+            self.area.0.from
+        } else {
+            self.area.0.to
+        };
+        self.back = if at.0 == line_start {
+            self.back_rightward = !self.back_rightward;
+            Coord(at.0, wrapping_dec(at.1, self.matrix_size.1))
+        } else if self.back_rightward {
+            Coord(wrapping_dec(at.0, self.matrix_size.0), at.1)
+        } else {
+            Coord(wrapping_inc(at.0, self.matrix_size.0), at.1)
+        };
+        Some((at, addr))
+    }
+}
+
+#[cfg(test)]
+mod scan_order_tests {
+    use super::*;
+
+    /// 从`interval.from`开始依次经过的值
+    fn walk(size: isize, interval: Interval<isize>) -> Vec<isize> {
+        (0..measure_length(size, interval))
+            .map(|i| (interval.from + i).rem_euclid(size))
+            .collect()
+    }
+
+    fn row_major(size: Coord<isize>, area: Coord<Interval<isize>>) -> Vec<Coord<isize>> {
+        let xs = walk(size.0, area.0);
+        walk(size.1, area.1)
+            .into_iter()
+            .flat_map(|y| xs.iter().map(move |x| Coord(*x, y)))
+            .collect()
+    }
+
+    fn column_major(size: Coord<isize>, area: Coord<Interval<isize>>) -> Vec<Coord<isize>> {
+        let ys = walk(size.1, area.1);
+        walk(size.0, area.0)
+            .into_iter()
+            .flat_map(|x| ys.iter().map(move |y| Coord(x, *y)))
+            .collect()
+    }
+
+    fn boustrophedon(size: Coord<isize>, area: Coord<Interval<isize>>) -> Vec<Coord<isize>> {
+        let xs = walk(size.0, area.0);
+        let mut cells = vec![];
+        for (i, y) in walk(size.1, area.1).into_iter().enumerate() {
+            let mut row: Vec<_> = xs.iter().map(|x| Coord(*x, y)).collect();
+            if i % 2 == 1 {
+                row.reverse();
+            }
+            cells.extend(row);
+        }
+        cells
+    }
+
+    /// 检查正向、反向和从两端交替遍历的结果
+    fn check<'m, Access, const CW: usize, const CH: usize>(
+        make: impl Fn() -> Iterator<'m, String, Access, CW, CH>,
+        expected: Vec<Coord<isize>>,
+    ) where
+        Access: DoubleEndedAccessor<CW, CH>,
+    {
+        let forward: Vec<_> = make()
+            .map(|(pos, value)| {
+                assert_eq!(*value, pos.to_string());
+                pos
+            })
+            .collect();
+        assert_eq!(forward, expected);
+
+        let backward: Vec<_> = make().rev().map(|(pos, _)| pos).collect();
+        let mut reversed = expected.clone();
+        reversed.reverse();
+        assert_eq!(backward, reversed);
+
+        for split in 0..=expected.len() {
+            let mut iter = make();
+            let mut front = vec![];
+            let mut back = vec![];
+            for _ in 0..split {
+                front.push(iter.next().unwrap().0);
+            }
+            while let Some((pos, _)) = iter.next_back() {
+                back.push(pos);
+                assert_eq!(iter.len(), expected.len() - front.len() - back.len());
+            }
+            assert_eq!(iter.next(), None);
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, expected);
+        }
+    }
+
+    fn test_sub<const CW: usize, const CH: usize>() {
+        let size = Coord(5, 4);
+        let matrix = Matrix::<String, CW, CH>::with_ctor(&Coord(5, 4), |opt_pos| {
+            opt_pos.map_or(String::new(), |pos| pos.to_string())
+        });
+        for y0 in 0..size.1 {
+            for y1 in 0..size.1 {
+                for x0 in 0..size.0 {
+                    for x1 in 0..size.0 {
+                        let area = Coord(x0, y0) | Coord(x1, y1);
+                        check(|| matrix.scan(area), row_major(size, area));
+                        check(|| matrix.column_scan(area), column_major(size, area));
+                        check(|| matrix.serpentine(area), boustrophedon(size, area));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scan_orders() {
+        test_sub::<1, 1>();
+        test_sub::<2, 3>();
+        test_sub::<4, 4>();
+    }
+}
@@ -0,0 +1,209 @@
+/// 库外定义访问顺序的方式：只需要给出位置，偏移量由[`Checked`]计算。
+///
+/// 所有方法都是安全的，实现有错误时只会得到错误的遍历结果：
+/// - [`next`](Shape::next)返回的位置可以超出矩阵，会先标准化
+/// - [`len`](Shape::len)只用于[`ExactSizeIterator::len`]
+/// - [`super_area`](Shape::super_area)会先标准化
+pub trait Shape {
+    /// 返回下一个格子的位置
+    fn next(&mut self) -> Option<Coord<isize>>;
+
+    /// 剩余的格子数
+    fn len(&self) -> usize;
+
+    /// 包含所有格子的区域
+    fn super_area(&self) -> Coord<Interval<isize>>;
+
+    /// 判断`pos`是否是访问的格子之一，`pos`已经标准化
+    fn contains(&self, pos: Coord<isize>) -> bool;
+
+    fn r#type(&self) -> &'static str {
+        "MShape"
+    }
+}
+
+/// 把[`Shape`]包装成[`Accessor`]，见[`Matrix::checked`]
+#[derive(Clone)]
+pub struct Checked<S> {
+    shape: S,
+    torus: Torus,
+}
+
+#[allow(dead_code)]
+impl<S: Shape> Checked<S> {
+    #[inline]
+    fn new(matrix_size: Coord<isize>, shape: S) -> Self {
+        Self {
+            shape,
+            torus: Torus::new(matrix_size),
+        }
+    }
+}
+
+// SAFETY: 偏移量由标准化以后的位置计算
+unsafe impl<S, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Accessor<CHUNK_WIDTH, CHUNK_HEIGHT> for Checked<S>
+where
+    S: Shape,
+{
+    #[inline]
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        let pos = self.torus.normalize(self.shape.next()?);
+        let addr = Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address(self.torus.size(), pos);
+        Some((pos, addr))
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.shape.len()
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        let area = self.shape.super_area();
+        self.torus.normalize(area.from()) | self.torus.normalize(area.to())
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.shape.contains(self.torus.normalize(pos))
+    }
+
+    fn r#type(&self) -> &'static str {
+        self.shape.r#type()
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.torus.size()
+    }
+}
+
+#[cfg(test)]
+mod shape_tests {
+    use super::*;
+
+    type M = Matrix<String, 3, 2>;
+
+    fn matrix() -> M {
+        M::with_ctor(&Coord(5, 4), |opt_pos| {
+            opt_pos.map_or("padding".to_string(), |pos| pos.to_string())
+        })
+    }
+
+    /// 一条对角线
+    #[derive(Clone)]
+    struct Diagonal {
+        at: isize,
+        length: isize,
+    }
+
+    impl Shape for Diagonal {
+        fn next(&mut self) -> Option<Coord<isize>> {
+            if self.at == self.length {
+                return None;
+            }
+            self.at += 1;
+            Some(Coord(self.at - 1, self.at - 1))
+        }
+
+        fn len(&self) -> usize {
+            (self.length - self.at) as usize
+        }
+
+        fn super_area(&self) -> Coord<Interval<isize>> {
+            Coord(0, 0) | Coord(self.length - 1, self.length - 1)
+        }
+
+        fn contains(&self, pos: Coord<isize>) -> bool {
+            pos.0 == pos.1 && pos.0 < self.length
+        }
+    }
+
+    /// 所有方法都给出错误或极端的结果
+    #[derive(Clone)]
+    struct Malicious {
+        at: usize,
+    }
+
+    const MALICIOUS: [Coord<isize>; 7] = [
+        Coord(isize::MAX, isize::MIN),
+        Coord(-1, -1),
+        Coord(isize::MIN, 0),
+        Coord(5, 4),
+        Coord(1_000_003, -999_999),
+        Coord(isize::MAX, isize::MAX),
+        Coord(-6, 17),
+    ];
+
+    impl Shape for Malicious {
+        fn next(&mut self) -> Option<Coord<isize>> {
+            self.at += 1;
+            MALICIOUS.get(self.at - 1).copied()
+        }
+
+        fn len(&self) -> usize {
+            usize::MAX
+        }
+
+        fn super_area(&self) -> Coord<Interval<isize>> {
+            Coord(isize::MAX, isize::MIN) | Coord(isize::MIN, isize::MAX)
+        }
+
+        fn contains(&self, _: Coord<isize>) -> bool {
+            true
+        }
+
+        fn r#type(&self) -> &'static str {
+            "Malicious"
+        }
+    }
+
+    #[test]
+    fn test_user_shape() {
+        let matrix = matrix();
+        let visited: Vec<_> = matrix
+            .select_shape(Diagonal { at: 0, length: 4 })
+            .map(|(pos, value)| {
+                assert_eq!(*value, pos.to_string());
+                pos
+            })
+            .collect();
+        assert_eq!(
+            visited,
+            vec![Coord(0, 0), Coord(1, 1), Coord(2, 2), Coord(3, 3)]
+        );
+        assert_eq!(matrix.select_shape(Diagonal { at: 1, length: 4 }).len(), 3);
+
+        let union = matrix
+            .checked(Diagonal { at: 0, length: 4 })
+            .union(matrix.scanner(Coord(0, 1) | Coord(4, 1)));
+        assert_eq!(matrix.select(union).count(), 4 + 5 - 1);
+    }
+
+    #[test]
+    fn test_malicious_shape() {
+        let matrix = matrix();
+        let torus = matrix.torus();
+        let visited: Vec<_> = matrix
+            .select_shape(Malicious { at: 0 })
+            .map(|(pos, value)| (pos, value.clone()))
+            .collect();
+        assert_eq!(visited.len(), MALICIOUS.len());
+        for ((pos, value), raw) in visited.into_iter().zip(MALICIOUS) {
+            assert_eq!(pos, torus.normalize(raw));
+            assert_eq!(value, pos.to_string());
+            assert_ne!(value, "padding");
+        }
+
+        let text = matrix.select_shape(Malicious { at: 0 }).to_string();
+        assert!(text.starts_with("Malicious"));
+        assert!(!text.contains("padding"));
+
+        let combined = matrix
+            .checked(Malicious { at: 0 })
+            .intersection(matrix.scanner(Coord(0, 0) | Coord(4, 3)))
+            .union(matrix.checked(Malicious { at: 3 }))
+            .step_by(Coord(1, 1));
+        for (pos, value) in matrix.select(combined) {
+            assert_eq!(*value, pos.to_string());
+        }
+    }
+}
@@ -0,0 +1,147 @@
+/// 以随机顺序访问区域中每个格子的访问器，见[`Matrix::shuffled`]
+///
+/// 访问顺序只取决于随机数生成器和区域，和分块大小无关。
+#[derive(Clone)]
+pub struct Shuffled<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    /// 按行排列的区域内下标，前`visited`个已经访问过
+    indices: Vec<usize>,
+    visited: usize,
+    rng: Pcg32,
+
+    area: Coord<Interval<isize>>,
+
+    matrix_size: Coord<isize>,
+}
+
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Shuffled<CHUNK_WIDTH, CHUNK_HEIGHT> {
+    /// `area`必须已经标准化
+    fn new(matrix_size: Coord<isize>, area: Coord<Interval<isize>>, rng: Pcg32) -> Self {
+        let length = measure_area(matrix_size, area).merge(std::ops::Mul::mul) as usize;
+        Self {
+            indices: (0..length).collect(),
+            visited: 0,
+            rng,
+            area,
+            matrix_size,
+        }
+    }
+}
+
+// SAFETY: 位置都经过标准化，偏移量由标准化以后的位置计算
+unsafe impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Accessor<CHUNK_WIDTH, CHUNK_HEIGHT>
+    for Shuffled<CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    fn next(&mut self) -> Option<(Coord<isize>, usize)> {
+        let rest = self.indices.len() - self.visited;
+        if rest == 0 {
+            return None;
+        }
+        // 每次只做Fisher-Yates洗牌的一步
+        let chosen = self.visited + self.rng.below(rest as u64) as usize;
+        self.indices.swap(self.visited, chosen);
+        let index = self.indices[self.visited];
+        self.visited += 1;
+
+        let pos = area_cell(self.matrix_size, self.area, index);
+        let addr = Matrix::<(), CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address(self.matrix_size, pos);
+        Some((pos, addr))
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.indices.len() - self.visited
+    }
+
+    fn super_area(&self) -> Coord<Interval<isize>> {
+        self.area
+    }
+
+    fn contains(&self, pos: Coord<isize>) -> bool {
+        self.area.contains(&pos)
+    }
+
+    fn r#type(&self) -> &'static str {
+        "MShuffled"
+    }
+
+    fn matrix_size(&self) -> Coord<isize> {
+        self.matrix_size
+    }
+}
+
+/// 按行数，`area`中第`index`个格子的位置，结果已经标准化
+#[inline]
+fn area_cell(
+    matrix_size: Coord<isize>,
+    area: Coord<Interval<isize>>,
+    index: usize,
+) -> Coord<isize> {
+    let width = measure_length(matrix_size.0, area.0);
+    let index = index as isize;
+    let pos = area.from() + Coord(index % width, index / width);
+    pos.reduce(matrix_size, isize::rem_euclid)
+}
+
+#[cfg(test)]
+mod shuffled_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn visit<const CW: usize, const CH: usize>(
+        area: Coord<Interval<isize>>,
+        seed: u64,
+    ) -> Vec<Coord<isize>> {
+        let matrix = Matrix::<String, CW, CH>::with_ctor(&Coord(7, 5), |opt_pos| {
+            opt_pos.map_or(String::new(), |pos| pos.to_string())
+        });
+        let mut rng = Pcg32::new(seed, 0);
+        let iter = matrix.shuffled(area, &mut rng);
+        assert_eq!(iter.len(), matrix.area(area).len());
+        iter.map(|(pos, value)| {
+            assert_eq!(*value, pos.to_string());
+            pos
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_shuffled() {
+        for area in [
+            Coord(0, 0) | Coord(6, 4),
+            Coord(5, 3) | Coord(1, 1),
+            Coord(2, 2) | Coord(2, 2),
+            Coord(-3, 4) | Coord(8, 6),
+        ] {
+            let expected: HashSet<_> = Matrix::<(), 1, 1>::new(&Coord(7, 5))
+                .area(area)
+                .map(|(pos, _)| pos)
+                .collect();
+            let order = visit::<1, 1>(area, 5);
+            assert_eq!(order.len(), expected.len());
+            assert_eq!(order.iter().copied().collect::<HashSet<_>>(), expected);
+            assert_eq!(visit::<2, 3>(area, 5), order);
+            assert_eq!(visit::<4, 4>(area, 5), order);
+        }
+        let area = Coord(0, 0) | Coord(6, 4);
+        assert_ne!(visit::<1, 1>(area, 5), visit::<1, 1>(area, 6));
+    }
+
+    #[test]
+    fn test_random_cell() {
+        let matrix = Matrix::<String, 2, 2>::with_ctor(&Coord(7, 5), |opt_pos| {
+            opt_pos.map_or(String::new(), |pos| pos.to_string())
+        });
+        let area = Coord(5, 4) | Coord(1, 0);
+        let expected: HashSet<_> = matrix.area(area).map(|(pos, _)| pos).collect();
+        let mut rng = Pcg32::new(3, 3);
+        let mut seen = HashSet::new();
+        for _ in 0..500 {
+            let (pos, value) = matrix.random_cell(area, &mut rng);
+            assert_eq!(*value, pos.to_string());
+            assert!(expected.contains(&pos));
+            seen.insert(pos);
+        }
+        assert_eq!(seen, expected);
+    }
+}
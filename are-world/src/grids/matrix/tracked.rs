@@ -0,0 +1,325 @@
+/// 记录哪些块被修改过的[`Matrix`]
+///
+/// 通过[`Deref`](std::ops::Deref)可以使用[`Matrix`]的所有只读方法；
+/// 所有修改都经过[`Tracked`]，被修改的块记录在位图中。
+/// 关闭记录以后，修改只多一次分支判断。
+pub struct Tracked<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> {
+    matrix: Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+    /// 第`i`位表示偏移量在`i * CHUNK_WIDTH * CHUNK_HEIGHT`开始的块
+    dirty: Vec<u64>,
+    enabled: bool,
+}
+
+#[allow(dead_code)]
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>
+    Tracked<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    /// 开始记录`matrix`的修改，一开始没有块被修改过
+    pub fn new(matrix: Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>) -> Self {
+        let chunk_count = matrix.elements.len() / (CHUNK_WIDTH * CHUNK_HEIGHT);
+        Self {
+            matrix,
+            dirty: vec![0; chunk_count.div_ceil(64)],
+            enabled: true,
+        }
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.matrix
+    }
+
+    /// 打开或关闭记录，已经记录的块不变
+    #[inline]
+    pub fn set_tracking(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    #[inline]
+    pub fn is_tracking(&self) -> bool {
+        self.enabled
+    }
+
+    /// 块`chunk`是否被修改过
+    ///
+    /// 和格子的位置一样，`chunk`按块的数量在每个维度上循环。
+    pub fn is_dirty(&self, chunk: Coord<isize>) -> bool {
+        let chunk_size = self.chunk_size();
+        let chunk = Coord(
+            chunk.0.rem_euclid(chunk_size.0 as isize),
+            chunk.1.rem_euclid(chunk_size.1 as isize),
+        );
+        let index = chunk.0 as usize + chunk.1 as usize * chunk_size.0;
+        self.dirty[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// 被修改过的块的位置，按在内存中的顺序排列
+    pub fn dirty_chunks(&self) -> impl std::iter::Iterator<Item = Coord<isize>> + '_ {
+        let chunk_row_count = self.chunk_size().0;
+        self.dirty.iter().enumerate().flat_map(move |(i, word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| {
+                    let index = i * 64 + bit;
+                    Coord(
+                        (index % chunk_row_count) as isize,
+                        (index / chunk_row_count) as isize,
+                    )
+                })
+        })
+    }
+
+    /// 包含所有被修改过的块的区域，没有块被修改过时返回[`None`]
+    pub fn dirty_area(&self) -> Option<Coord<Interval<isize>>> {
+        let size = self.matrix.size;
+        self.dirty_chunks()
+            .map(|chunk| {
+                let from = chunk * Coord(CHUNK_WIDTH as isize, CHUNK_HEIGHT as isize);
+                let to = (from + Coord(CHUNK_WIDTH as isize, CHUNK_HEIGHT as isize))
+                    .reduce(size, std::cmp::min);
+                from | (to - Coord(1, 1))
+            })
+            .reduce(|a, b| a.bounding_union(b, size))
+    }
+
+    /// 忘记所有修改
+    #[inline]
+    pub fn clear_dirty(&mut self) {
+        self.dirty.fill(0);
+    }
+
+    /// 按行遍历`area`，可以修改每个格子；`area`覆盖的块都记为修改过
+    pub fn area_mut(
+        &mut self,
+        area: Coord<Interval<isize>>,
+    ) -> IterMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.mark_area(area);
+        self.matrix.area_mut(area)
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, Element, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.area_mut(Coord(0, 0) | (*self.size() - Coord(1, 1)))
+    }
+
+    /// 见[`Matrix::apply`]，补丁修改的块都记为修改过
+    pub fn apply(&mut self, patch: &Patch<Element>) -> Result<(), MatrixError>
+    where
+        Element: Clone,
+    {
+        self.matrix.apply(patch)?;
+        if self.enabled {
+            for (pos, _) in patch.cells() {
+                self.mark_addr(Matrix::<Element, CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address(
+                    self.matrix.size,
+                    pos,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 见[`Matrix::apply_each`]，所有块都记为修改过
+    pub fn apply_each(&mut self, f: impl FnMut(Coord<isize>, &mut Element)) {
+        self.matrix.apply_each(f);
+        self.mark_area(Coord(0, 0) | (*self.size() - Coord(1, 1)));
+    }
+
+    /// 见[`Matrix::fill_area`]，`area`覆盖的块都记为修改过
+    pub fn fill_area(&mut self, area: Coord<Interval<isize>>, value: &Element)
+    where
+        Element: Clone,
+    {
+        self.mark_area(area);
+        self.matrix.fill_area(area, value);
+    }
+
+    /// 见[`Matrix::fill_area_with`]，`area`覆盖的块都记为修改过
+    pub fn fill_area_with(
+        &mut self,
+        area: Coord<Interval<isize>>,
+        constructor: impl FnMut(Coord<isize>) -> Element,
+    ) {
+        self.mark_area(area);
+        self.matrix.fill_area_with(area, constructor);
+    }
+
+    /// 见[`Matrix::replace_where`]，`area`覆盖的块都记为修改过
+    pub fn replace_where(
+        &mut self,
+        area: Coord<Interval<isize>>,
+        predicate: impl FnMut(Coord<isize>, &Element) -> bool,
+        value: &Element,
+    ) -> usize
+    where
+        Element: Clone,
+    {
+        self.mark_area(area);
+        self.matrix.replace_where(area, predicate, value)
+    }
+
+    /// 见[`Matrix::swap_areas`]，成功时两个区域覆盖的块都记为修改过
+    pub fn swap_areas(
+        &mut self,
+        a: Coord<Interval<isize>>,
+        b: Coord<Interval<isize>>,
+    ) -> Result<(), MatrixError> {
+        self.matrix.swap_areas(a, b)?;
+        self.mark_area(a);
+        self.mark_area(b);
+        Ok(())
+    }
+
+    /// 把`area`覆盖的块都记为修改过
+    pub fn mark_area(&mut self, area: Coord<Interval<isize>>) {
+        if !self.enabled {
+            return;
+        }
+        let size = self.matrix.size;
+        let chunk_row_count = self.chunk_size().0;
+        for piece in self.matrix.normalize_area(area).split_at_seams(size) {
+            for chunk_y in piece.1.from / CHUNK_HEIGHT as isize..=piece.1.to / CHUNK_HEIGHT as isize
+            {
+                for chunk_x in
+                    piece.0.from / CHUNK_WIDTH as isize..=piece.0.to / CHUNK_WIDTH as isize
+                {
+                    let index = chunk_x as usize + chunk_y as usize * chunk_row_count;
+                    self.dirty[index / 64] |= 1 << (index % 64);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn mark_addr(&mut self, addr: usize) {
+        if self.enabled {
+            let index = addr / (CHUNK_WIDTH * CHUNK_HEIGHT);
+            self.dirty[index / 64] |= 1 << (index % 64);
+        }
+    }
+
+    #[inline]
+    fn chunk_size(&self) -> Coord<usize> {
+        let size = self.matrix.size;
+        Matrix::<Element, CHUNK_WIDTH, CHUNK_HEIGHT>::calc_chunk_size(Coord(
+            size.0 as usize,
+            size.1 as usize,
+        ))
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::Deref
+    for Tracked<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    type Target = Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.matrix
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::Index<Coord<isize>>
+    for Tracked<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    type Output = Element;
+
+    #[inline]
+    fn index(&self, index: Coord<isize>) -> &Element {
+        &self.matrix[index]
+    }
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> std::ops::IndexMut<Coord<isize>>
+    for Tracked<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+{
+    fn index_mut(&mut self, index: Coord<isize>) -> &mut Element {
+        let addr =
+            Matrix::<Element, CHUNK_WIDTH, CHUNK_HEIGHT>::calc_address(self.matrix.size, index);
+        self.mark_addr(addr);
+        unsafe { self.matrix.get_by_addr_mut(addr) }
+    }
+}
+
+#[cfg(test)]
+mod tracked_tests {
+    use super::*;
+
+    type T = Tracked<i32, 3, 2>;
+
+    fn tracked() -> T {
+        Tracked::new(Matrix::new(&Coord(8, 5)))
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut matrix = tracked();
+        assert_eq!(matrix.dirty_area(), None);
+        matrix[Coord(4, 3)] = 1;
+        matrix[Coord(-1, -1)] = 2;
+        assert_eq!(matrix[Coord(7, 4)], 2);
+        assert_eq!(
+            matrix.dirty_chunks().collect::<Vec<_>>(),
+            vec![Coord(1, 1), Coord(2, 2)]
+        );
+        assert!(matrix.is_dirty(Coord(1, 1)));
+        assert!(!matrix.is_dirty(Coord(0, 0)));
+        assert!(matrix.is_dirty(Coord(-1, -1)));
+        assert!(matrix.is_dirty(Coord(4, 4)));
+        assert!(!matrix.is_dirty(Coord(3, 0)));
+        assert_eq!(matrix.dirty_area(), Some(Coord(3, 2) | Coord(7, 4)));
+
+        matrix.clear_dirty();
+        assert_eq!(matrix.dirty_chunks().count(), 0);
+        assert_eq!(matrix.iter().filter(|(_, v)| **v != 0).count(), 2);
+    }
+
+    #[test]
+    fn test_area_mut() {
+        let mut matrix = tracked();
+        for (pos, value) in matrix.area_mut(Coord(7, 4) | Coord(0, 0)) {
+            *value = (pos.0 + 10 * pos.1) as i32;
+        }
+        assert_eq!(
+            matrix.dirty_chunks().collect::<Vec<_>>(),
+            vec![Coord(0, 0), Coord(2, 0), Coord(0, 2), Coord(2, 2)]
+        );
+        assert_eq!(matrix[Coord(7, 0)], 7);
+        assert_eq!(matrix[Coord(0, 4)], 40);
+        assert_eq!(matrix.iter().filter(|(_, v)| **v != 0).count(), 3);
+        // 跨过边界的区域
+        assert_eq!(matrix.dirty_area(), Some(Coord(6, 4) | Coord(2, 1)));
+
+        let mut matrix = tracked();
+        let mut count = 0;
+        for (pos, value) in matrix.iter_mut().rev() {
+            *value = pos.0 as i32;
+            count += 1;
+        }
+        assert_eq!(count, 40);
+        assert_eq!(matrix.dirty_chunks().count(), 9);
+        assert!(matrix.iter().all(|(pos, value)| *value == pos.0 as i32));
+    }
+
+    #[test]
+    fn test_disabled_and_patch() {
+        let mut matrix = tracked();
+        matrix.set_tracking(false);
+        matrix[Coord(0, 0)] = 1;
+        matrix.iter_mut().for_each(|(_, value)| *value += 1);
+        assert_eq!(matrix.dirty_chunks().count(), 0);
+        assert_eq!(matrix[Coord(0, 0)], 2);
+
+        matrix.set_tracking(true);
+        let mut target = matrix.clone();
+        target[Coord(5, 4)] = 9;
+        let patch = matrix.diff(&target).unwrap();
+        matrix.apply(&patch).unwrap();
+        assert_eq!(matrix[Coord(5, 4)], 9);
+        assert_eq!(matrix.dirty_chunks().collect::<Vec<_>>(), vec![Coord(1, 2)]);
+
+        let wrong = Matrix::<i32, 3, 2>::new(&Coord(2, 2));
+        let patch = wrong.diff(&wrong).unwrap();
+        assert!(matrix.apply(&patch).is_err());
+    }
+}
@@ -4,9 +4,21 @@ pub use interval::*;
 pub use isizeconv::*;
 pub use matrix::*;
 pub use matrix::*;
+pub use path::*;
+pub use torus::*;
 
 pub mod coord;
+pub mod coord3;
 pub mod coord_interval;
+pub mod field;
+pub mod hex;
 pub mod interval;
+pub mod interval_set;
 pub mod isizeconv;
 pub mod matrix;
+pub mod noise;
+pub mod path;
+pub mod summed_area;
+#[cfg(test)]
+pub mod testing;
+pub mod torus;
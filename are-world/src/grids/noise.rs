@@ -0,0 +1,301 @@
+//! 首尾相接的程序化噪声
+//!
+//! 噪声在格点之间插值得到。格点数总是矩阵大小的整数分之一，
+//! 格点在边界处按环面取模，所以生成的矩阵在左右、上下边界处都是连续的。
+
+use std::f64::consts::{FRAC_1_SQRT_2, TAU};
+
+use super::{Coord, Matrix};
+use crate::rng::Pcg32;
+
+/// 噪声的种类
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum NoiseKind {
+    /// 在格点的随机值之间插值
+    Value,
+    /// 在格点的随机梯度之间插值（Perlin噪声）
+    Gradient,
+}
+
+/// [`Matrix::generate`]的参数
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NoiseParams {
+    pub kind: NoiseKind,
+    /// 第一层噪声相邻格点之间大约隔多少格，应该大于0；
+    /// 格点数最少是1，最多是矩阵的大小
+    pub scale: f64,
+    /// 最多叠加的层数；格点数超过矩阵大小的层不再叠加
+    pub octaves: u32,
+    /// 每一层的振幅是上一层的多少倍
+    pub persistence: f64,
+    /// 每一层的格点数是上一层的多少倍，至少是1；必须是整数，否则不能首尾相接
+    pub lacunarity: u32,
+}
+
+impl Default for NoiseParams {
+    fn default() -> Self {
+        Self {
+            kind: NoiseKind::Gradient,
+            scale: 16.0,
+            octaves: 4,
+            persistence: 0.5,
+            lacunarity: 2,
+        }
+    }
+}
+
+/// 值噪声，结果在`[0, 1]`之间。
+///
+/// `pos`是格点坐标，两个维度分别以`period`为周期。
+#[allow(dead_code)]
+pub fn value_noise(seed: u64, period: Coord<isize>, pos: Coord<f64>) -> f64 {
+    interpolate(period, pos, |corner, _| {
+        lattice_rng(seed, period, corner).next_f64()
+    })
+}
+
+/// 梯度噪声，结果在`[0, 1]`之间，格点上总是`0.5`。
+///
+/// `pos`是格点坐标，两个维度分别以`period`为周期。
+#[allow(dead_code)]
+pub fn gradient_noise(seed: u64, period: Coord<isize>, pos: Coord<f64>) -> f64 {
+    let noise = interpolate(period, pos, |corner, offset| {
+        let angle = lattice_rng(seed, period, corner).next_f64() * TAU;
+        angle.cos() * offset.0 + angle.sin() * offset.1
+    });
+    // 单位梯度的二维Perlin噪声在`[-√½, √½]`之间
+    (noise / FRAC_1_SQRT_2 * 0.5 + 0.5).clamp(0.0, 1.0)
+}
+
+/// 把`params.octaves`层噪声叠加起来，得到`size`大小的环面上`pos`处的值，结果在`[0, 1]`之间。
+///
+/// 格点数超过`size`以后，后面的层比格子还细，不再叠加。
+///
+/// *`params.lacunarity`是0时panic*
+#[allow(dead_code)]
+pub fn fractal_noise(
+    seed: u64,
+    size: Coord<isize>,
+    pos: Coord<isize>,
+    params: &NoiseParams,
+) -> f64 {
+    assert!(params.lacunarity > 0, "lacunarity must be positive");
+    let lacunarity = params.lacunarity as isize;
+    let mut period = size.map(|s| (s as f64 / params.scale).round().clamp(1.0, s as f64) as isize);
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut amplitudes = 0.0;
+    for octave in 0..params.octaves {
+        let at = Coord(
+            pos.0 as f64 * period.0 as f64 / size.0 as f64,
+            pos.1 as f64 * period.1 as f64 / size.1 as f64,
+        );
+        let octave_seed = Pcg32::new(seed, octave as u64).next_u64();
+        let noise = match params.kind {
+            NoiseKind::Value => value_noise(octave_seed, period, at),
+            NoiseKind::Gradient => gradient_noise(octave_seed, period, at),
+        };
+        total += noise * amplitude;
+        amplitudes += amplitude;
+        amplitude *= params.persistence;
+        let next = period
+            .0
+            .checked_mul(lacunarity)
+            .zip(period.1.checked_mul(lacunarity));
+        match next {
+            Some((x, y)) if x <= size.0 && y <= size.1 => period = Coord(x, y),
+            _ => break,
+        }
+    }
+    if amplitudes > 0.0 {
+        total / amplitudes
+    } else {
+        0.0
+    }
+}
+
+#[allow(dead_code)]
+impl<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> Matrix<f64, CHUNK_WIDTH, CHUNK_HEIGHT> {
+    /// 用[`fractal_noise`]填充矩阵，同样的参数总是得到同样的结果
+    pub fn generate(size: &Coord<usize>, seed: u64, params: NoiseParams) -> Self {
+        let isize_size = Coord(size.0 as isize, size.1 as isize);
+        Self::with_ctor(size, |opt_pos| {
+            opt_pos.map_or(0.0, |pos| fractal_noise(seed, isize_size, pos, &params))
+        })
+    }
+
+    /// 不小于`level`的格子是`true`
+    pub fn threshold(&self, level: f64) -> Matrix<bool, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        self.map_cells(|value| value >= level)
+    }
+
+    /// 按`levels`把每个格子分到`tiles`中的一类：
+    /// 小于`levels[0]`的是`tiles[0]`，在`levels[i - 1]`和`levels[i]`之间的是`tiles[i]`，依此类推。
+    ///
+    /// *`levels`没有从小到大排列，或者`tiles`不比`levels`多一个的时候panic*
+    pub fn quantize<T: Clone>(
+        &self,
+        levels: &[f64],
+        tiles: &[T],
+    ) -> Matrix<T, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        assert_eq!(
+            tiles.len(),
+            levels.len() + 1,
+            "there must be one more tile than levels"
+        );
+        assert!(
+            levels.windows(2).all(|w| w[0] <= w[1]),
+            "levels must be sorted"
+        );
+        self.map_cells(|value| tiles[levels.partition_point(|level| *level <= value)].clone())
+    }
+
+    fn map_cells<T>(&self, f: impl Fn(f64) -> T) -> Matrix<T, CHUNK_WIDTH, CHUNK_HEIGHT> {
+        let size = *self.size();
+        Matrix::with_ctor(&Coord(size.0 as usize, size.1 as usize), |opt_pos| {
+            f(opt_pos.map_or(0.0, |pos| self[pos]))
+        })
+    }
+}
+
+/// 用`corner_value(格点, pos相对格点的偏移)`求出四个角的值，再用平滑曲线插值
+fn interpolate(
+    period: Coord<isize>,
+    pos: Coord<f64>,
+    corner_value: impl Fn(Coord<isize>, Coord<f64>) -> f64,
+) -> f64 {
+    let cell = pos.map(f64::floor);
+    let t = Coord(pos.0 - cell.0, pos.1 - cell.1);
+    let base = Coord(cell.0 as isize, cell.1 as isize).reduce(period, isize::rem_euclid);
+    let value = |dx: isize, dy: isize| {
+        corner_value(
+            base + Coord(dx, dy),
+            Coord(t.0 - dx as f64, t.1 - dy as f64),
+        )
+    };
+    let fade = |t: f64| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+    let (u, v) = (fade(t.0), fade(t.1));
+    lerp(
+        lerp(value(0, 0), value(1, 0), u),
+        lerp(value(0, 1), value(1, 1), u),
+        v,
+    )
+}
+
+/// 格点`corner`专用的随机数，`corner`先按`period`取模
+#[inline]
+fn lattice_rng(seed: u64, period: Coord<isize>, corner: Coord<isize>) -> Pcg32 {
+    Pcg32::for_cell(seed, 0, corner.reduce(period, isize::rem_euclid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type M = Matrix<f64, 3, 2>;
+
+    #[test]
+    fn test_periodic() {
+        let period = Coord(5, 3);
+        for i in 0..40 {
+            let pos = Coord(i as f64 * 0.37 - 3.0, i as f64 * 0.61 - 7.0);
+            let shifted = Coord(pos.0 + 5.0, pos.1 - 6.0);
+            for noise in [value_noise, gradient_noise] {
+                let a = noise(11, period, pos);
+                let b = noise(11, period, shifted);
+                assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+                assert!((0.0..=1.0).contains(&a));
+            }
+            let lattice = Coord(pos.0.floor(), pos.1.floor());
+            assert!((gradient_noise(11, period, lattice) - 0.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_seamless() {
+        for kind in [NoiseKind::Value, NoiseKind::Gradient] {
+            let params = NoiseParams {
+                kind,
+                scale: 6.0,
+                ..NoiseParams::default()
+            };
+            let size = Coord(30, 20);
+            let matrix = M::generate(&Coord(30, 20), 7, params);
+            // 跨过边界的相邻格子和内部的相邻格子差别一样小
+            let mut inner = 0.0f64;
+            let mut seam = 0.0f64;
+            for (pos, value) in matrix.iter() {
+                assert!((0.0..=1.0).contains(value));
+                for step in [Coord(1, 0), Coord(0, 1)] {
+                    let diff = (matrix[pos + step] - value).abs();
+                    if pos.0 == size.0 - 1 || pos.1 == size.1 - 1 {
+                        seam = seam.max(diff);
+                    } else {
+                        inner = inner.max(diff);
+                    }
+                }
+            }
+            assert!(seam <= inner, "{:?}: seam {} > inner {}", kind, seam, inner);
+            assert!(inner < 0.5);
+        }
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let params = NoiseParams::default();
+        let a = M::generate(&Coord(17, 9), 3, params);
+        let b = Matrix::<f64, 4, 4>::generate(&Coord(17, 9), 3, params);
+        let c = M::generate(&Coord(17, 9), 4, params);
+        assert!(a.iter().all(|(pos, value)| b[pos] == *value));
+        assert!(a.iter().any(|(pos, value)| c[pos] != *value));
+    }
+
+    #[test]
+    fn test_extreme_params() {
+        let params = NoiseParams {
+            octaves: 70,
+            lacunarity: 3,
+            ..NoiseParams::default()
+        };
+        let matrix = M::generate(&Coord(17, 9), 5, params);
+        assert!(matrix.iter().all(|(_, value)| (0.0..=1.0).contains(value)));
+
+        let params = NoiseParams {
+            scale: 0.0,
+            ..NoiseParams::default()
+        };
+        let matrix = M::generate(&Coord(17, 9), 5, params);
+        assert!(matrix.iter().all(|(_, value)| (0.0..=1.0).contains(value)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_lacunarity_panics() {
+        let params = NoiseParams {
+            lacunarity: 0,
+            ..NoiseParams::default()
+        };
+        fractal_noise(1, Coord(8, 8), Coord(3, 3), &params);
+    }
+
+    #[test]
+    fn test_threshold_and_quantize() {
+        let matrix = M::with_ctor(&Coord(4, 3), |opt_pos| {
+            opt_pos.map_or(0.0, |pos| (pos.0 + 4 * pos.1) as f64 / 12.0)
+        });
+        let land = matrix.threshold(0.5);
+        for (pos, value) in matrix.iter() {
+            assert_eq!(land[pos], *value >= 0.5);
+        }
+
+        let tiles = matrix.quantize(&[0.25, 0.5, 0.75], &['~', '.', '^', 'A']);
+        assert_eq!(tiles[Coord(0, 0)], '~');
+        assert_eq!(tiles[Coord(2, 0)], '~');
+        assert_eq!(tiles[Coord(3, 0)], '.');
+        assert_eq!(tiles[Coord(2, 1)], '^');
+        assert_eq!(tiles[Coord(1, 2)], 'A');
+        assert_eq!(matrix.quantize(&[], &[1])[Coord(3, 2)], 1);
+    }
+}
@@ -0,0 +1,240 @@
+//! 环面矩阵上的寻路
+//!
+//! 矩阵的左右、上下两边是相连的，最短路径经常会穿过边界。
+//! 这里的[`dijkstra`]和[`a_star`]都把矩阵当作环面处理，
+//! 返回的路径中所有坐标都经过[`Matrix::normalize`]。
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::{Coord, Matrix, Torus};
+
+/// 每一步可以移动到哪些相邻的格子
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Neighborhood {
+    /// 上下左右四个方向
+    Four,
+    /// 上下左右加上四个对角，共八个方向
+    Eight,
+}
+
+#[allow(dead_code)]
+impl Neighborhood {
+    /// 每个方向相对于当前格子的偏移量。
+    /// 顺序是固定的，依赖它的算法在平局时会得到确定的结果。
+    #[inline]
+    pub const fn offsets(self) -> &'static [Coord<isize>] {
+        const FOUR: [Coord<isize>; 4] = [Coord(0, -1), Coord(-1, 0), Coord(1, 0), Coord(0, 1)];
+        const EIGHT: [Coord<isize>; 8] = [
+            Coord(-1, -1),
+            Coord(0, -1),
+            Coord(1, -1),
+            Coord(-1, 0),
+            Coord(1, 0),
+            Coord(-1, 1),
+            Coord(0, 1),
+            Coord(1, 1),
+        ];
+        match self {
+            Neighborhood::Four => &FOUR,
+            Neighborhood::Eight => &EIGHT,
+        }
+    }
+
+    /// 在`size`大小的环面上，`a`和`b`之间至少要走多少步
+    #[inline]
+    pub fn steps(self, size: Coord<isize>, a: Coord<isize>, b: Coord<isize>) -> u64 {
+        let torus = Torus::new(size);
+        let steps = match self {
+            Neighborhood::Four => torus.manhattan(a, b),
+            Neighborhood::Eight => torus.chebyshev(a, b),
+        };
+        steps as u64
+    }
+}
+
+/// 用Dijkstra算法寻找从`from`到`to`代价最小的路径。
+///
+/// `cost(pos, element)`是进入`pos`格子的代价，返回[`None`]表示不能通过。
+/// 出发的格子不计算代价。
+///
+/// 找到时返回包括起点和终点在内的路径，不可达时返回[`None`]。
+#[allow(dead_code)]
+pub fn dijkstra<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+    matrix: &Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+    from: Coord<isize>,
+    to: Coord<isize>,
+    neighborhood: Neighborhood,
+    cost: impl Fn(Coord<isize>, &Element) -> Option<u32>,
+) -> Option<Vec<Coord<isize>>> {
+    a_star(matrix, from, to, neighborhood, 0, cost)
+}
+
+/// 用A*算法寻找从`from`到`to`代价最小的路径。
+///
+/// `min_cost`是`cost`可能返回的最小值，
+/// 启发函数是环面上的最少步数乘以`min_cost`，所以只要`min_cost`不大于实际的代价，结果就是最优的。
+/// `min_cost`为`0`时和[`dijkstra`]相同。
+///
+/// 其余参数和返回值见[`dijkstra`]。
+#[allow(dead_code)]
+pub fn a_star<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+    matrix: &Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+    from: Coord<isize>,
+    to: Coord<isize>,
+    neighborhood: Neighborhood,
+    min_cost: u32,
+    cost: impl Fn(Coord<isize>, &Element) -> Option<u32>,
+) -> Option<Vec<Coord<isize>>> {
+    let size = *matrix.size();
+    let from = matrix.normalize(from);
+    let to = matrix.normalize(to);
+    let heuristic = |pos: Coord<isize>| neighborhood.steps(size, pos, to) * min_cost as u64;
+
+    let usize_size = Coord(size.0 as usize, size.1 as usize);
+    let mut best = Matrix::<Option<u64>, CHUNK_WIDTH, CHUNK_HEIGHT>::new(&usize_size);
+    let mut came_from = Matrix::<Option<Coord<isize>>, CHUNK_WIDTH, CHUNK_HEIGHT>::new(&usize_size);
+    // 按(f, g, y, x)排序，代价相同时的选择是确定的
+    let mut open = BinaryHeap::new();
+
+    best[from] = Some(0);
+    open.push(Reverse((heuristic(from), 0u64, from.1, from.0)));
+
+    while let Some(Reverse((_, g, y, x))) = open.pop() {
+        let pos = Coord(x, y);
+        if best[pos].is_some_and(|b| b < g) {
+            continue;
+        }
+        if pos == to {
+            let mut path = vec![pos];
+            let mut at = pos;
+            while let Some(prev) = came_from[at] {
+                path.push(prev);
+                at = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for offset in neighborhood.offsets() {
+            let next = matrix.normalize(pos + *offset);
+            let step = match cost(next, &matrix[next]) {
+                Some(step) => step as u64,
+                None => continue,
+            };
+            let next_g = g + step;
+            if best[next].is_none_or(|b| next_g < b) {
+                best[next] = Some(next_g);
+                came_from[next] = Some(pos);
+                open.push(Reverse((next_g + heuristic(next), next_g, next.1, next.0)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type M = Matrix<u32, 2, 3>;
+
+    fn is_adjacent(size: Coord<isize>, n: Neighborhood, a: Coord<isize>, b: Coord<isize>) -> bool {
+        n.steps(size, a, b) == 1
+    }
+
+    fn path_cost(matrix: &M, path: &[Coord<isize>]) -> u64 {
+        path.iter().skip(1).map(|p| matrix[*p] as u64).sum()
+    }
+
+    fn check_path(
+        matrix: &M,
+        n: Neighborhood,
+        from: Coord<isize>,
+        to: Coord<isize>,
+        path: &[Coord<isize>],
+    ) {
+        let size = *matrix.size();
+        assert_eq!(path.first(), Some(&matrix.normalize(from)));
+        assert_eq!(path.last(), Some(&matrix.normalize(to)));
+        for p in path {
+            assert_eq!(*p, matrix.normalize(*p));
+            assert_ne!(matrix[*p], 0);
+        }
+        for w in path.windows(2) {
+            assert!(is_adjacent(size, n, w[0], w[1]));
+        }
+    }
+
+    /// `0`表示墙，其余是进入格子的代价
+    fn cost(_: Coord<isize>, e: &u32) -> Option<u32> {
+        if *e == 0 {
+            None
+        } else {
+            Some(*e)
+        }
+    }
+
+    #[test]
+    fn test_wrap_both_axes() {
+        let matrix = M::with_fill(&Coord(10, 8), &1);
+        for n in [Neighborhood::Four, Neighborhood::Eight] {
+            let path = a_star(&matrix, Coord(1, 1), Coord(8, 6), n, 1, cost).unwrap();
+            check_path(&matrix, n, Coord(1, 1), Coord(8, 6), &path);
+            let expected = match n {
+                Neighborhood::Four => 3 + 3,
+                Neighborhood::Eight => 3,
+            };
+            assert_eq!(path.len(), expected + 1);
+            assert!(path.iter().any(|p| p.0 == 0) && path.iter().any(|p| p.0 == 9));
+            assert!(path.iter().any(|p| p.1 == 0) && path.iter().any(|p| p.1 == 7));
+        }
+    }
+
+    #[test]
+    fn test_walls_force_seam() {
+        // 第4列和第3行是墙，只留下穿过边界的路
+        let matrix = M::with_ctor(&Coord(9, 7), |opt_pos| match opt_pos {
+            Some(Coord(4, _)) | Some(Coord(_, 3)) => 0,
+            _ => 1,
+        });
+        let from = Coord(2, 1);
+        let to = Coord(6, 5);
+        let path = dijkstra(&matrix, from, to, Neighborhood::Four, cost).unwrap();
+        check_path(&matrix, Neighborhood::Four, from, to, &path);
+        assert_eq!(path.len(), 5 + 3 + 1);
+
+        let blocked = M::with_ctor(&Coord(9, 7), |opt_pos| match opt_pos {
+            Some(Coord(4, _)) | Some(Coord(0, _)) => 0,
+            _ => 1,
+        });
+        assert_eq!(
+            dijkstra(&blocked, from, to, Neighborhood::Eight, cost),
+            None
+        );
+    }
+
+    #[test]
+    fn test_a_star_matches_dijkstra() {
+        let matrix = M::with_ctor(&Coord(11, 9), |opt_pos| {
+            opt_pos.map_or(0, |p| ((p.0 * 7 + p.1 * 13 + p.0 * p.1) % 5) as u32)
+        });
+        for n in [Neighborhood::Four, Neighborhood::Eight] {
+            for (from, to) in [
+                (Coord(1, 0), Coord(9, 7)),
+                (Coord(1, 1), Coord(-3, 17)),
+                (Coord(5, 4), Coord(5, 4)),
+                (Coord(8, 0), Coord(2, 6)),
+            ] {
+                assert_ne!(matrix[from], 0, "{} is a wall", from);
+                assert_ne!(matrix[to], 0, "{} is a wall", to);
+                let d = dijkstra(&matrix, from, to, n, cost).unwrap();
+                let a = a_star(&matrix, from, to, n, 1, cost).unwrap();
+                check_path(&matrix, n, from, to, &d);
+                check_path(&matrix, n, from, to, &a);
+                assert_eq!(path_cost(&matrix, &d), path_cost(&matrix, &a));
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! 二维前缀和
+//!
+//! 构造一次以后，任意区域（包括跨过边界的区域）的和都可以在常数时间内求出。
+
+use std::ops::{Add, Sub};
+
+use super::{Coord, Interval, Matrix, Torus};
+
+/// 矩阵的二维前缀和，见[`SummedArea::sum`]
+///
+/// 矩阵改变以后需要重新构造。
+#[derive(Clone, Debug)]
+pub struct SummedArea<T> {
+    /// `(size.0 + 1) * (size.1 + 1)`个前缀和，
+    /// `Coord(x, y)`处是`Coord(0, 0)`到`Coord(x - 1, y - 1)`之间所有格子的和
+    table: Vec<T>,
+    torus: Torus,
+}
+
+#[allow(dead_code)]
+impl<T> SummedArea<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// 对`matrix`中的值求前缀和
+    pub fn new<const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+        matrix: &Matrix<T, CHUNK_WIDTH, CHUNK_HEIGHT>,
+    ) -> Self {
+        Self::with_value(matrix, |value| *value)
+    }
+
+    /// 对`matrix`中每个格子的`value(element)`求前缀和
+    pub fn with_value<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+        matrix: &Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+        value: impl Fn(&Element) -> T,
+    ) -> Self {
+        let size = *matrix.size();
+        let stride = size.0 as usize + 1;
+        let mut table = vec![T::default(); stride * (size.1 as usize + 1)];
+        for (pos, element) in matrix.iter() {
+            let (x, y) = (pos.0 as usize, pos.1 as usize);
+            // 先加后减，无符号类型也不会溢出
+            table[(y + 1) * stride + x + 1] =
+                value(element) + table[y * stride + x + 1] + table[(y + 1) * stride + x]
+                    - table[y * stride + x];
+        }
+        Self {
+            table,
+            torus: Torus::new(size),
+        }
+    }
+
+    /// 矩阵的大小
+    #[inline]
+    pub fn size(&self) -> Coord<isize> {
+        self.torus.size()
+    }
+
+    /// 所有格子的和
+    #[inline]
+    pub fn total(&self) -> T {
+        *self.table.last().unwrap()
+    }
+
+    /// `area`中所有格子的和，`area`会先标准化
+    pub fn sum(&self, area: Coord<Interval<isize>>) -> T {
+        let area = self.torus.normalize(area.from()) | self.torus.normalize(area.to());
+        area.split_at_seams(self.size())
+            .into_iter()
+            .fold(T::default(), |sum, piece| sum + self.sum_unwrapped(piece))
+    }
+
+    /// `area`中格子的平均值，和用`to_f64`转换成[`f64`]，`area`会先标准化。
+    ///
+    /// 用于没有实现`Into<f64>`的类型，比如`usize`和`i64`：`sums.mean_by(area, |sum| sum as f64)`
+    pub fn mean_by(&self, area: Coord<Interval<isize>>, to_f64: impl FnOnce(T) -> f64) -> f64 {
+        let area = self.torus.normalize(area.from()) | self.torus.normalize(area.to());
+        to_f64(self.sum(area)) / area.area_len(self.size()) as f64
+    }
+
+    /// 不跨过边界的`area`中所有格子的和
+    #[inline]
+    fn sum_unwrapped(&self, area: Coord<Interval<isize>>) -> T {
+        let at =
+            |x: isize, y: isize| self.table[y as usize * (self.size().0 as usize + 1) + x as usize];
+        let Coord(x0, y0) = area.from();
+        let Coord(x1, y1) = area.to() + Coord(1, 1);
+        at(x1, y1) + at(x0, y0) - at(x0, y1) - at(x1, y0)
+    }
+}
+
+#[allow(dead_code)]
+impl<T> SummedArea<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Into<f64>,
+{
+    /// `area`中格子的平均值，`area`会先标准化
+    pub fn mean(&self, area: Coord<Interval<isize>>) -> f64 {
+        self.mean_by(area, Into::into)
+    }
+}
+
+#[allow(dead_code)]
+impl SummedArea<usize> {
+    /// 统计`predicate(element)`为`true`的格子，[`sum`](SummedArea::sum)返回区域中这样的格子数
+    pub fn count_where<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize>(
+        matrix: &Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>,
+        predicate: impl Fn(&Element) -> bool,
+    ) -> Self {
+        Self::with_value(matrix, |element| predicate(element) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Pcg32;
+
+    fn random_area(rng: &mut Pcg32, size: Coord<isize>) -> Coord<Interval<isize>> {
+        let mut random = || Coord(rng.below(3 * size.0 as u64), rng.below(3 * size.1 as u64));
+        let (from, to) = (random(), random());
+        let shift = |c: Coord<u64>| Coord(c.0 as isize - size.0, c.1 as isize - size.1);
+        shift(from) | shift(to)
+    }
+
+    fn test_sub<const CW: usize, const CH: usize>(size: Coord<usize>, seed: u64) {
+        let mut rng = Pcg32::new(seed, 0);
+        let matrix = Matrix::<u32, CW, CH>::with_ctor(&size, |_| rng.below(100) as u32);
+        let sums = SummedArea::new(&matrix);
+        let counts = SummedArea::count_where(&matrix, |v| *v < 30);
+        let signed = SummedArea::with_value(&matrix, |v| *v as i64 - 50);
+
+        let isize_size = Coord(size.0 as isize, size.1 as isize);
+        assert_eq!(sums.total(), matrix.iter().map(|(_, v)| *v).sum::<u32>());
+        for _ in 0..200 {
+            let area = random_area(&mut rng, isize_size);
+            let cells: Vec<u32> = matrix.area(area).map(|(_, v)| *v).collect();
+            assert_eq!(sums.sum(area), cells.iter().sum::<u32>(), "{}", area);
+            assert_eq!(counts.sum(area), cells.iter().filter(|v| **v < 30).count());
+            assert_eq!(
+                signed.sum(area),
+                cells.iter().map(|v| *v as i64 - 50).sum::<i64>()
+            );
+            let mean = cells.iter().sum::<u32>() as f64 / cells.len() as f64;
+            assert!((sums.mean(area) - mean).abs() < 1e-9);
+            let ratio = cells.iter().filter(|v| **v < 30).count() as f64 / cells.len() as f64;
+            assert!((counts.mean_by(area, |count| count as f64) - ratio).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        test_sub::<1, 1>(Coord(1, 1), 1);
+        test_sub::<2, 3>(Coord(7, 5), 2);
+        test_sub::<4, 4>(Coord(13, 9), 3);
+        test_sub::<8, 2>(Coord(40, 31), 4);
+    }
+}
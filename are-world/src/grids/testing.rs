@@ -0,0 +1,496 @@
+//! 对照测试：把同一串随机操作分别作用在[`Matrix`]和朴素的[`ReferenceGrid`]上，
+//! 找出第一个结果不同的操作。
+//!
+//! 其他网格类型实现[`TestGrid`]以后也可以用[`check`]和参考实现对照。
+
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::rng::Pcg32;
+
+use super::{coord::Coord, interval::Interval, matrix::Matrix};
+
+/// 被对照测试的网格
+pub trait TestGrid<Element>: Sized {
+    /// 构造`size`大小的网格，`(x, y)`处的元素是`constructor(Coord(x, y))`
+    fn with_ctor(size: Coord<usize>, constructor: impl FnMut(Coord<isize>) -> Element) -> Self;
+    fn size(&self) -> Coord<isize>;
+    /// `pos`会先标准化
+    fn get(&self, pos: Coord<isize>) -> &Element;
+    /// `pos`会先标准化
+    fn set(&mut self, pos: Coord<isize>, value: Element);
+    /// 按[`Scan`](super::matrix::Scan)的顺序列出`area`中的格子，`area`会先标准化
+    fn scan(&self, area: Coord<Interval<isize>>) -> Vec<(Coord<isize>, Element)>;
+    /// 保留重叠部分，其余格子用`Element::default()`填充
+    fn resize(&mut self, size: Coord<usize>);
+    /// 把`area`画成表格，`None`表示整个网格
+    fn format(&self, area: Option<Coord<Interval<isize>>>) -> String;
+}
+
+/// 按行存储、不分块的网格，作为对照的参考实现
+#[derive(Clone, Debug)]
+pub struct ReferenceGrid<Element> {
+    size: Coord<isize>,
+    cells: Vec<Element>,
+}
+
+impl<Element> ReferenceGrid<Element> {
+    #[inline]
+    fn index(&self, pos: Coord<isize>) -> usize {
+        let x = pos.0.rem_euclid(self.size.0);
+        let y = pos.1.rem_euclid(self.size.1);
+        (y * self.size.0 + x) as usize
+    }
+
+    /// 从`from`开始依次经过`interval`中的值
+    fn walk(size: isize, interval: Interval<isize>) -> Vec<isize> {
+        let from = interval.from.rem_euclid(size);
+        let to = interval.to.rem_euclid(size);
+        let len = if from <= to {
+            to - from + 1
+        } else {
+            size - from + to + 1
+        };
+        (0..len).map(|i| (from + i) % size).collect()
+    }
+}
+
+impl<Element> TestGrid<Element> for ReferenceGrid<Element>
+where
+    Element: Clone + Default + ToString,
+{
+    fn with_ctor(size: Coord<usize>, mut constructor: impl FnMut(Coord<isize>) -> Element) -> Self {
+        let size = Coord(size.0 as isize, size.1 as isize);
+        let mut cells = Vec::with_capacity((size.0 * size.1) as usize);
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                cells.push(constructor(Coord(x, y)));
+            }
+        }
+        Self { size, cells }
+    }
+
+    #[inline]
+    fn size(&self) -> Coord<isize> {
+        self.size
+    }
+
+    #[inline]
+    fn get(&self, pos: Coord<isize>) -> &Element {
+        &self.cells[self.index(pos)]
+    }
+
+    #[inline]
+    fn set(&mut self, pos: Coord<isize>, value: Element) {
+        let index = self.index(pos);
+        self.cells[index] = value;
+    }
+
+    fn scan(&self, area: Coord<Interval<isize>>) -> Vec<(Coord<isize>, Element)> {
+        let xs = Self::walk(self.size.0, area.0);
+        let mut cells = vec![];
+        for y in Self::walk(self.size.1, area.1) {
+            for x in xs.iter() {
+                cells.push((Coord(*x, y), self.get(Coord(*x, y)).clone()));
+            }
+        }
+        cells
+    }
+
+    fn resize(&mut self, size: Coord<usize>) {
+        let old = std::mem::replace(self, Self::with_ctor(size, |_| Element::default()));
+        for y in 0..old.size.1.min(self.size.1) {
+            for x in 0..old.size.0.min(self.size.0) {
+                self.set(Coord(x, y), old.get(Coord(x, y)).clone());
+            }
+        }
+    }
+
+    /// 和[`Matrix`]画同样的表格：第一行和第一列是下标，逗号分隔，每一列居中对齐
+    fn format(&self, area: Option<Coord<Interval<isize>>>) -> String {
+        let (head, area) = match area {
+            Some(area) => ("MScan", area),
+            None => ("M", Coord(0, 0) | (self.size - Coord(1, 1))),
+        };
+        let xs = Self::walk(self.size.0, area.0);
+        let mut rows = vec![std::iter::once(head.to_string())
+            .chain(xs.iter().map(|x| x.to_string()))
+            .collect::<Vec<_>>()];
+        for y in Self::walk(self.size.1, area.1) {
+            let cells = xs.iter().map(|x| quote(self.get(Coord(*x, y)).to_string()));
+            rows.push(std::iter::once(y.to_string()).chain(cells).collect());
+        }
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|i| rows.iter().map(|row| row[i].len()).max().unwrap())
+            .collect();
+        rows.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(widths.iter())
+                    .map(|(cell, width)| format!("{:^width$}", cell, width = width))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 空串写成`""`；含有引号、空白或逗号时加上引号，引号写两遍，`\r`、`\n`、`\t`转义
+fn quote(raw: String) -> String {
+    if raw.is_empty() {
+        return "\"\"".to_string();
+    }
+    if !raw.contains(['"', '\r', '\n', '\t', ' ', ',']) {
+        return raw;
+    }
+    let escaped = raw
+        .replace('"', "\"\"")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+impl<Element, const CHUNK_WIDTH: usize, const CHUNK_HEIGHT: usize> TestGrid<Element>
+    for Matrix<Element, CHUNK_WIDTH, CHUNK_HEIGHT>
+where
+    Element: Clone + Default + ToString,
+{
+    fn with_ctor(size: Coord<usize>, mut constructor: impl FnMut(Coord<isize>) -> Element) -> Self {
+        Matrix::with_ctor(&size, |opt_pos| {
+            opt_pos.map_or_else(Element::default, &mut constructor)
+        })
+    }
+
+    #[inline]
+    fn size(&self) -> Coord<isize> {
+        *Matrix::size(self)
+    }
+
+    #[inline]
+    fn get(&self, pos: Coord<isize>) -> &Element {
+        &self[pos]
+    }
+
+    #[inline]
+    fn set(&mut self, pos: Coord<isize>, value: Element) {
+        self[pos] = value;
+    }
+
+    fn scan(&self, area: Coord<Interval<isize>>) -> Vec<(Coord<isize>, Element)> {
+        self.area(area)
+            .map(|(pos, element)| (pos, element.clone()))
+            .collect()
+    }
+
+    #[inline]
+    fn resize(&mut self, size: Coord<usize>) {
+        Matrix::resize(self, &size);
+    }
+
+    fn format(&self, area: Option<Coord<Interval<isize>>>) -> String {
+        match area {
+            Some(area) => self.area(area).to_string(),
+            None => self.to_string(),
+        }
+    }
+}
+
+/// 对网格的一次操作
+#[derive(Clone, Debug)]
+pub enum Op<Element> {
+    Get(Coord<isize>),
+    Set(Coord<isize>, Element),
+    Scan(Coord<Interval<isize>>),
+    Resize(Coord<usize>),
+    Format(Option<Coord<Interval<isize>>>),
+}
+
+/// 一次对照测试：先构造`size`大小、按行填入`cells`的网格，再依次执行`ops`
+#[derive(Clone, Debug)]
+pub struct Case<Element> {
+    pub size: Coord<usize>,
+    pub cells: Vec<Element>,
+    pub ops: Vec<Op<Element>>,
+}
+
+/// 第一个结果不同的操作
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// 被测网格的类型名
+    pub grid: String,
+    /// 出错的操作在[`Case::ops`]中的下标，构造时出错为`None`
+    pub step: Option<usize>,
+    pub op: String,
+    pub expected: String,
+    pub found: String,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.step {
+            Some(step) => write!(f, "{} diverged at step {} ({})", self.grid, step, self.op)?,
+            None => write!(f, "{} diverged on construction", self.grid)?,
+        }
+        write!(f, "\nexpected:\n{}\nfound:\n{}", self.expected, self.found)
+    }
+}
+
+/// 随机生成的坐标在`-2 * size ..= 3 * size`中，覆盖环绕的情况
+fn random_pos(rng: &mut Pcg32, size: Coord<usize>) -> Coord<isize> {
+    let mut axis = |len: usize| rng.below(5 * len as u64 + 1) as isize - 2 * len as isize;
+    Coord(axis(size.0), axis(size.1))
+}
+
+fn random_size(rng: &mut Pcg32, max_size: Coord<usize>) -> Coord<usize> {
+    Coord(
+        rng.below(max_size.0 as u64) as usize + 1,
+        rng.below(max_size.1 as u64) as usize + 1,
+    )
+}
+
+impl<Element> Op<Element> {
+    /// 在`size`大小的网格上随机生成一个操作，`Resize`的大小不超过`max_size`
+    pub fn random(
+        rng: &mut Pcg32,
+        size: Coord<usize>,
+        max_size: Coord<usize>,
+        mut value: impl FnMut(&mut Pcg32) -> Element,
+    ) -> Self {
+        match rng.below(16) {
+            0..=3 => Op::Get(random_pos(rng, size)),
+            4..=9 => {
+                let pos = random_pos(rng, size);
+                Op::Set(pos, value(rng))
+            }
+            10..=12 => Op::Scan(random_pos(rng, size) | random_pos(rng, size)),
+            13 => Op::Resize(random_size(rng, max_size)),
+            14 => Op::Format(None),
+            _ => Op::Format(Some(random_pos(rng, size) | random_pos(rng, size))),
+        }
+    }
+}
+
+impl<Element> Case<Element> {
+    /// 随机生成不超过`max_size`大小的网格和`len`个操作
+    pub fn random(
+        rng: &mut Pcg32,
+        max_size: Coord<usize>,
+        len: usize,
+        mut value: impl FnMut(&mut Pcg32) -> Element,
+    ) -> Self {
+        let size = random_size(rng, max_size);
+        let cells = (0..size.0 * size.1).map(|_| value(rng)).collect();
+        let mut current = size;
+        let mut ops = Vec::with_capacity(len);
+        for _ in 0..len {
+            let op = Op::random(rng, current, max_size, &mut value);
+            if let Op::Resize(size) = op {
+                current = size;
+            }
+            ops.push(op);
+        }
+        Self { size, cells, ops }
+    }
+}
+
+/// 一次操作的结果
+#[derive(PartialEq, Debug)]
+enum Outcome<Element> {
+    None,
+    Value(Element),
+    Cells(Vec<(Coord<isize>, Element)>),
+    Text(String),
+}
+
+fn perform<Element, G>(grid: &mut G, op: &Op<Element>) -> Outcome<Element>
+where
+    Element: Clone,
+    G: TestGrid<Element>,
+{
+    match op {
+        Op::Get(pos) => Outcome::Value(grid.get(*pos).clone()),
+        Op::Set(pos, value) => {
+            grid.set(*pos, value.clone());
+            Outcome::None
+        }
+        Op::Scan(area) => Outcome::Cells(grid.scan(*area)),
+        Op::Resize(size) => {
+            grid.resize(*size);
+            Outcome::None
+        }
+        Op::Format(area) => Outcome::Text(grid.format(*area)),
+    }
+}
+
+/// 网格的大小和全部格子
+fn snapshot<Element, G>(grid: &G) -> (Coord<isize>, Vec<Element>)
+where
+    Element: Clone,
+    G: TestGrid<Element>,
+{
+    let size = grid.size();
+    let cells = (0..size.1)
+        .flat_map(|y| (0..size.0).map(move |x| Coord(x, y)))
+        .map(|pos| grid.get(pos).clone())
+        .collect();
+    (size, cells)
+}
+
+/// 把`case`分别作用在`G`和[`ReferenceGrid`]上，
+/// 比较每个操作的结果和操作以后网格的内容，返回第一处不同
+pub fn check<Element, G>(case: &Case<Element>) -> Result<(), Divergence>
+where
+    Element: Clone + Default + ToString + PartialEq + Debug,
+    G: TestGrid<Element>,
+{
+    let ctor =
+        |pos: Coord<isize>| case.cells[(pos.1 * case.size.0 as isize + pos.0) as usize].clone();
+    let mut expected = ReferenceGrid::with_ctor(case.size, ctor);
+    let mut found = G::with_ctor(case.size, ctor);
+    let diverge = |step, op: String, expected: String, found: String| Divergence {
+        grid: std::any::type_name::<G>().to_string(),
+        step,
+        op,
+        expected,
+        found,
+    };
+
+    let (expected_state, found_state) = (snapshot(&expected), snapshot(&found));
+    if expected_state != found_state {
+        return Err(diverge(
+            None,
+            format!("{:?}", case.size),
+            format!("{:?}", expected_state),
+            format!("{:?}", found_state),
+        ));
+    }
+    for (step, op) in case.ops.iter().enumerate() {
+        let expected_outcome = perform(&mut expected, op);
+        let found_outcome = perform(&mut found, op);
+        if expected_outcome != found_outcome {
+            return Err(diverge(
+                Some(step),
+                format!("{:?}", op),
+                format!("{:?}", expected_outcome),
+                format!("{:?}", found_outcome),
+            ));
+        }
+        let (expected_state, found_state) = (snapshot(&expected), snapshot(&found));
+        if expected_state != found_state {
+            return Err(diverge(
+                Some(step),
+                format!("{:?}", op),
+                format!("{:?}", expected_state),
+                format!("{:?}", found_state),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 对多种分块大小的[`Matrix`]执行[`check`]
+pub fn check_chunk_sizes<Element>(case: &Case<Element>) -> Result<(), Divergence>
+where
+    Element: Clone + Default + ToString + PartialEq + Debug,
+{
+    check::<Element, Matrix<Element, 1, 1>>(case)?;
+    check::<Element, Matrix<Element, 1, 3>>(case)?;
+    check::<Element, Matrix<Element, 2, 1>>(case)?;
+    check::<Element, Matrix<Element, 2, 2>>(case)?;
+    check::<Element, Matrix<Element, 3, 2>>(case)?;
+    check::<Element, Matrix<Element, 2, 5>>(case)?;
+    check::<Element, Matrix<Element, 4, 4>>(case)?;
+    check::<Element, Matrix<Element, 7, 3>>(case)?;
+    check::<Element, Matrix<Element, 8, 8>>(case)?;
+    check::<Element, Matrix<Element, 16, 1>>(case)?;
+    Ok(())
+}
+
+/// 用`seed`生成`count`个随机的[`Case`]，依次执行[`check_chunk_sizes`]
+pub fn check_random(seed: u64, count: usize) -> Result<(), Divergence> {
+    let mut rng = Pcg32::new(seed, 0);
+    for _ in 0..count {
+        let case = Case::random(&mut rng, Coord(12, 10), 40, |rng| rng.below(100));
+        check_chunk_sizes(&case)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 写入时忽略第一列的网格，用来确认对照测试能发现错误
+    struct SkipFirstColumn(ReferenceGrid<u64>);
+
+    impl TestGrid<u64> for SkipFirstColumn {
+        fn with_ctor(size: Coord<usize>, constructor: impl FnMut(Coord<isize>) -> u64) -> Self {
+            Self(ReferenceGrid::with_ctor(size, constructor))
+        }
+        fn size(&self) -> Coord<isize> {
+            self.0.size()
+        }
+        fn get(&self, pos: Coord<isize>) -> &u64 {
+            self.0.get(pos)
+        }
+        fn set(&mut self, pos: Coord<isize>, value: u64) {
+            if pos.0.rem_euclid(self.size().0) != 0 {
+                self.0.set(pos, value);
+            }
+        }
+        fn scan(&self, area: Coord<Interval<isize>>) -> Vec<(Coord<isize>, u64)> {
+            self.0.scan(area)
+        }
+        fn resize(&mut self, size: Coord<usize>) {
+            self.0.resize(size);
+        }
+        fn format(&self, area: Option<Coord<Interval<isize>>>) -> String {
+            self.0.format(area)
+        }
+    }
+
+    #[test]
+    fn test_matrix() {
+        check_random(0x5eed, 60).unwrap_or_else(|divergence| panic!("{}", divergence));
+    }
+
+    #[test]
+    fn test_format() {
+        let cells = ["a", "b c", "", "\"q\"", "12", "x,y"];
+        let ctor = |pos: Coord<isize>| cells[(pos.1 * 3 + pos.0) as usize].to_string();
+        let expected = [
+            "M,   0   ,  1  ,  2  ",
+            "0,   a   ,\"b c\", \"\"  ",
+            "1,\"\"\"q\"\"\", 12  ,\"x,y\"",
+        ]
+        .join("\n");
+        let reference = ReferenceGrid::with_ctor(Coord(3, 2), ctor);
+        let matrix = <Matrix<String, 2, 2> as TestGrid<String>>::with_ctor(Coord(3, 2), ctor);
+        assert_eq!(reference.format(None), expected);
+        assert_eq!(TestGrid::format(&matrix, None), expected);
+
+        let area = Some(Coord(2, 1) | Coord(0, 1));
+        let expected = "MScan,  2  ,   0   \n  1  ,\"x,y\",\"\"\"q\"\"\"";
+        assert_eq!(reference.format(area), expected);
+        assert_eq!(TestGrid::format(&matrix, area), expected);
+    }
+
+    #[test]
+    fn test_reports_divergence() {
+        let case = Case {
+            size: Coord(3, 2),
+            cells: vec![0; 6],
+            ops: vec![
+                Op::Set(Coord(1, 0), 1),
+                Op::Get(Coord(-3, 1)),
+                Op::Set(Coord(3, 1), 2),
+                Op::Get(Coord(0, 1)),
+            ],
+        };
+        assert_eq!(check::<u64, Matrix<u64, 2, 2>>(&case), Ok(()));
+        let divergence = check::<u64, SkipFirstColumn>(&case).unwrap_err();
+        assert_eq!(divergence.step, Some(2));
+        assert_eq!(divergence.op, "Set(Coord(3, 1), 2)");
+    }
+}
@@ -0,0 +1,321 @@
+//! 环面上的几何计算
+//!
+//! [`Matrix`](super::Matrix)的左右、上下两边是相连的，
+//! `Coord(0, 0)`和`Coord(size.0 - 1, 0)`是相邻的。
+//! [`Torus`]只保存大小，提供考虑环绕的位移、距离和插值。
+
+use super::{hex::Axial, Coord};
+
+/// 大小为`size`的环面
+///
+/// `size`的两个维度都必须大于0
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Torus {
+    size: Coord<isize>,
+}
+
+#[allow(dead_code)]
+impl Torus {
+    /// ```rust
+    /// return Torus{size};
+    #[inline]
+    pub const fn new(size: Coord<isize>) -> Self {
+        Self { size }
+    }
+
+    #[inline]
+    pub const fn size(&self) -> Coord<isize> {
+        self.size
+    }
+
+    /// 把`pos`变换到`Coord(0, 0)`和`size - Coord(1, 1)`之间
+    #[inline]
+    pub fn normalize(&self, pos: Coord<isize>) -> Coord<isize> {
+        pos.reduce(self.size, isize::rem_euclid)
+    }
+
+    /// 从`from`走到`to`的最短位移。
+    ///
+    /// 每个维度的结果`d`满足`-size / 2 < d <= size / 2`，
+    /// 所以两个方向一样远时取正方向。
+    #[inline]
+    pub fn displacement(&self, from: Coord<isize>, to: Coord<isize>) -> Coord<isize> {
+        (self.normalize(to - from)).reduce(self.size, |d, s| if d > s / 2 { d - s } else { d })
+    }
+
+    /// 只能上下左右移动时，`a`和`b`之间的步数
+    #[inline]
+    pub fn manhattan(&self, a: Coord<isize>, b: Coord<isize>) -> isize {
+        self.displacement(a, b)
+            .map(isize::abs)
+            .merge(std::ops::Add::add)
+    }
+
+    /// 也能沿对角线移动时，`a`和`b`之间的步数
+    #[inline]
+    pub fn chebyshev(&self, a: Coord<isize>, b: Coord<isize>) -> isize {
+        self.displacement(a, b).map(isize::abs).merge(std::cmp::max)
+    }
+
+    /// `a`和`b`之间直线距离的平方
+    #[inline]
+    pub fn euclidean_sq(&self, a: Coord<isize>, b: Coord<isize>) -> isize {
+        let d = self.displacement(a, b);
+        d.0 * d.0 + d.1 * d.1
+    }
+
+    /// 把矩阵看作“奇数行右移”（odd-r）的六边形网格时，`a`和`b`之间的步数。
+    ///
+    /// `size.1`必须是偶数，否则上下边界连接处的行错位不一致。
+    ///
+    /// *`size.1`是奇数时panic*
+    #[inline]
+    pub fn hex(&self, a: Coord<isize>, b: Coord<isize>) -> isize {
+        self.hex_displacement(a, b).steps()
+    }
+
+    /// 六边形网格上从`from`走到`to`的最短位移，用轴坐标表示。
+    ///
+    /// 有多条一样短的位移时，结果只由`to - from`标准化以后的值决定。
+    /// `size.1`必须是偶数，见[`Torus::hex`]
+    ///
+    /// *`size.1`是奇数时panic*
+    pub fn hex_displacement(&self, from: Coord<isize>, to: Coord<isize>) -> Axial {
+        assert!(self.size.1 % 2 == 0, "hex layout requires an even height");
+        let to_axial = |pos: Coord<isize>| Axial::from_offset(self.normalize(pos));
+        let Coord(width, height) = self.size;
+        let d = to_axial(to) - to_axial(from);
+
+        // 在轴坐标中，环面由(width, 0)和(-height / 2, height)两个平移生成
+        let mut best = d;
+        for j in -2..=2 {
+            let r = d.1 + j * height;
+            let q = d.0 - j * height / 2;
+            // 固定r时，q最好落在-r / 2附近
+            let i0 = (-r / 2 - q).div_euclid(width);
+            for i in i0 - 1..=i0 + 2 {
+                let candidate = Axial(q + i * width, r);
+                if candidate.steps() < best.steps() {
+                    best = candidate;
+                }
+            }
+        }
+        best
+    }
+
+    /// 六边形网格上和`pos`相邻的六个格子，已经标准化，顺序同[`Axial::DIRECTIONS`]。
+    ///
+    /// 环面很小的时候可能有重复的格子。`size.1`必须是偶数，见[`Torus::hex`]
+    #[inline]
+    pub fn hex_neighbors(&self, pos: Coord<isize>) -> [Coord<isize>; 6] {
+        let hex = Axial::from_offset(self.normalize(pos));
+        hex.neighbors().map(|n| self.normalize(n.to_offset()))
+    }
+
+    /// 六边形网格上沿最短位移从`a`到`b`的直线经过的格子，包括两端，已经标准化，见[`Axial::line`]。
+    ///
+    /// `size.1`必须是偶数，见[`Torus::hex`]
+    pub fn hex_line(&self, a: Coord<isize>, b: Coord<isize>) -> Vec<Coord<isize>> {
+        let start = Axial::from_offset(self.normalize(a));
+        let d = self.hex_displacement(a, b);
+        Axial(0, 0)
+            .line(d)
+            .into_iter()
+            .map(|step| self.normalize((start + step).to_offset()))
+            .collect()
+    }
+
+    /// 沿最短路径从`a`走到`b`的中点，结果已经标准化。
+    ///
+    /// 步数是奇数时，中点取靠近`a`的一侧。
+    #[inline]
+    pub fn midpoint(&self, a: Coord<isize>, b: Coord<isize>) -> Coord<isize> {
+        self.normalize(a + self.displacement(a, b) / Coord(2, 2))
+    }
+
+    /// 沿最短路径从`a`走到`b`，走到比例`t`处的位置，结果已经标准化。
+    ///
+    /// `t`为`0.0`时是`a`，为`1.0`时是`b`；每个维度四舍五入到整数。
+    #[inline]
+    pub fn lerp(&self, a: Coord<isize>, b: Coord<isize>, t: f64) -> Coord<isize> {
+        let d = self
+            .displacement(a, b)
+            .map(|d| (d as f64 * t).round() as isize);
+        self.normalize(a + d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grids::{field::distance_field, Matrix, Neighborhood};
+
+    const SIZES: [isize; 6] = [1, 2, 3, 4, 5, 7];
+
+    fn each_pair(size: Coord<isize>, mut f: impl FnMut(Coord<isize>, Coord<isize>)) {
+        for ay in 0..size.1 {
+            for ax in 0..size.0 {
+                for by in 0..size.1 {
+                    for bx in 0..size.0 {
+                        f(Coord(ax, ay), Coord(bx, by));
+                    }
+                }
+            }
+        }
+    }
+
+    /// 在`size`大小的环面上从`from`出发做广度优先搜索
+    fn bfs(size: Coord<isize>, from: Coord<isize>, n: Neighborhood) -> Matrix<Option<u32>, 2, 2> {
+        let matrix = Matrix::<(), 2, 2>::new(&Coord(size.0 as usize, size.1 as usize));
+        distance_field(&matrix, [from], n, |_, _| true)
+    }
+
+    #[test]
+    fn test_displacement() {
+        for w in SIZES {
+            for h in SIZES {
+                let torus = Torus::new(Coord(w, h));
+                each_pair(Coord(w, h), |a, b| {
+                    let d = torus.displacement(a, b);
+                    assert_eq!(torus.normalize(a + d), b);
+                    assert!(-w / 2 <= d.0 && d.0 <= w / 2);
+                    assert!(-h / 2 <= d.1 && d.1 <= h / 2);
+                    if w % 2 == 0 {
+                        assert_ne!(d.0, -w / 2);
+                    }
+                    // 平移后位移不变
+                    assert_eq!(
+                        torus.displacement(a + Coord(w, -3 * h), b + Coord(-w, h)),
+                        d
+                    );
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        for w in SIZES {
+            for h in SIZES {
+                let size = Coord(w, h);
+                let torus = Torus::new(size);
+                let mut bfs_from = None;
+                each_pair(size, |a, b| {
+                    if bfs_from.as_ref().is_none_or(|(from, _, _)| *from != a) {
+                        let four = bfs(size, a, Neighborhood::Four);
+                        let eight = bfs(size, a, Neighborhood::Eight);
+                        bfs_from = Some((a, four, eight));
+                    }
+                    let (_, four, eight) = bfs_from.as_ref().unwrap();
+                    assert_eq!(Some(torus.manhattan(a, b) as u32), four[b]);
+                    assert_eq!(Some(torus.chebyshev(a, b) as u32), eight[b]);
+                    let mut expected = isize::MAX;
+                    for i in -1..=1 {
+                        for j in -1..=1 {
+                            let d = b + Coord(i * w, j * h) - a;
+                            expected = std::cmp::min(expected, d.0 * d.0 + d.1 * d.1);
+                        }
+                    }
+                    assert_eq!(torus.euclidean_sq(a, b), expected);
+                });
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "hex layout requires an even height")]
+    fn test_hex_odd_height() {
+        Torus::new(Coord(4, 3)).hex(Coord(0, 0), Coord(1, 1));
+    }
+
+    #[test]
+    fn test_hex() {
+        const EVEN: [Coord<isize>; 6] = [
+            Coord(1, 0),
+            Coord(0, -1),
+            Coord(-1, -1),
+            Coord(-1, 0),
+            Coord(-1, 1),
+            Coord(0, 1),
+        ];
+        const ODD: [Coord<isize>; 6] = [
+            Coord(1, 0),
+            Coord(1, -1),
+            Coord(0, -1),
+            Coord(-1, 0),
+            Coord(0, 1),
+            Coord(1, 1),
+        ];
+        for size in [
+            Coord(1, 2),
+            Coord(3, 2),
+            Coord(4, 4),
+            Coord(5, 6),
+            Coord(9, 2),
+            Coord(2, 8),
+            Coord(7, 4),
+        ] {
+            let torus = Torus::new(size);
+            for ay in 0..size.1 {
+                for ax in 0..size.0 {
+                    let a = Coord(ax, ay);
+                    let mut dist = std::collections::HashMap::new();
+                    let mut queue = std::collections::VecDeque::from([a]);
+                    dist.insert(a, 0);
+                    while let Some(pos) = queue.pop_front() {
+                        let offsets = if pos.1 % 2 == 0 { EVEN } else { ODD };
+                        for offset in offsets {
+                            let next = torus.normalize(pos + offset);
+                            if !dist.contains_key(&next) {
+                                dist.insert(next, dist[&pos] + 1);
+                                queue.push_back(next);
+                            }
+                        }
+                    }
+                    for (b, d) in dist {
+                        assert_eq!(torus.hex(a, b), d, "{} -> {} in {}", a, b, size);
+                        assert_eq!(torus.hex(b, a), d);
+                        let hex_d = torus.hex_displacement(a, b);
+                        assert_eq!(torus.normalize((Axial::from(a) + hex_d).into()), b);
+                        let line = torus.hex_line(a, b);
+                        assert_eq!(line.len() as isize, d + 1);
+                        assert_eq!((line[0], line[d as usize]), (a, b));
+                        for pair in line.windows(2) {
+                            assert_eq!(torus.hex(pair[0], pair[1]), 1);
+                        }
+                    }
+                    let mut neighbors = torus.hex_neighbors(a).to_vec();
+                    let mut expected: Vec<_> = (if a.1 % 2 == 0 { EVEN } else { ODD })
+                        .iter()
+                        .map(|offset| torus.normalize(a + *offset))
+                        .collect();
+                    neighbors.sort_by_key(|pos| (pos.1, pos.0));
+                    expected.sort_by_key(|pos| (pos.1, pos.0));
+                    assert_eq!(neighbors, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_midpoint_and_lerp() {
+        for w in SIZES {
+            for h in SIZES {
+                let torus = Torus::new(Coord(w, h));
+                each_pair(Coord(w, h), |a, b| {
+                    let m = torus.midpoint(a, b);
+                    let ab = torus.chebyshev(a, b);
+                    assert!(torus.chebyshev(a, m) <= torus.chebyshev(m, b));
+                    assert!(torus.chebyshev(a, m) + torus.chebyshev(m, b) == ab);
+                    assert_eq!(torus.lerp(a, b, 0.0), a);
+                    assert_eq!(torus.lerp(a, b, 1.0), b);
+                    assert_eq!(torus.lerp(a - Coord(w, h), b + Coord(w, h), 1.0), b);
+                    let d = torus.displacement(a, b);
+                    if d.0 % 2 == 0 && d.1 % 2 == 0 {
+                        assert_eq!(torus.lerp(a, b, 0.5), m);
+                    }
+                });
+            }
+        }
+    }
+}
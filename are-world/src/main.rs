@@ -6,6 +6,7 @@ use crate::sword::SWord;
 
 mod grids;
 // mod pipes;
+mod rng;
 mod sword;
 
 fn main() {
@@ -0,0 +1,3 @@
+pub use pcg::*;
+
+pub mod pcg;
@@ -0,0 +1,199 @@
+//! # Pcg32
+//!
+//! 可复现的伪随机数生成器。
+//!
+//! 只使用定宽整数运算，同样的种子在任何平台上都得到同样的序列。
+
+use crate::grids::Coord;
+
+const MULTIPLIER: u64 = 6364136223846793005;
+
+/// PCG-XSH-RR 32位伪随机数生成器
+///
+/// ## Example:
+/// ```rust
+/// let mut rng = Pcg32::new(42, 54);
+/// assert_eq!(rng.next_u32(), 0xa15c02b7);
+///
+/// let mut child = rng.split(); // 和`rng`互相独立
+/// let cell = Pcg32::for_cell(42, 7, Coord(3, 4)); // 同样的参数总是得到同样的序列
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Pcg32 {
+    state: u64,
+    /// 必须是奇数
+    increment: u64,
+}
+
+#[allow(dead_code)]
+impl Pcg32 {
+    /// 用种子`seed`创建第`stream`条序列，不同的`stream`得到互相独立的序列
+    pub fn new(seed: u64, stream: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    /// 第`tick`回合中`pos`格子专用的序列。
+    ///
+    /// 只取决于参数，和访问格子的顺序无关；`pos`应该先标准化。
+    pub fn for_cell(seed: u64, tick: u64, pos: Coord<isize>) -> Self {
+        let key = [tick, pos.0 as i64 as u64, pos.1 as i64 as u64]
+            .into_iter()
+            .fold(mix(seed), |hash, word| mix(hash ^ word));
+        Self::new(key, mix(!key))
+    }
+
+    /// 从`self`中取出一个新的生成器，`self`会前进
+    pub fn split(&mut self) -> Self {
+        let seed = self.next_u64();
+        let stream = self.next_u64();
+        Self::new(seed, stream)
+    }
+
+    /// 根据`key`派生一个新的生成器，`self`不变。
+    ///
+    /// 同样的`self`和`key`总是得到同样的生成器。
+    pub fn fork(&self, key: u64) -> Self {
+        Self::new(mix(self.state ^ mix(key)), mix(self.increment ^ key))
+    }
+
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rotation = (old >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        let low = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    /// 在`0..bound`中均匀地取一个数
+    ///
+    /// *`bound`是0的时候panic*
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert_ne!(bound, 0, "bound must be positive");
+        // Lemire的乘法取模，拒绝低位落在偏差区间内的结果
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = self.next_u64() as u128 * bound as u128;
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// 在`[0, 1)`中均匀地取一个数
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// 以`p`的概率返回`true`
+    #[inline]
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+/// SplitMix64的混合函数
+#[inline]
+const fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_output() {
+        // PCG官方示例程序pcg32-demo的输出
+        let mut rng = Pcg32::new(42, 54);
+        let expected = [
+            0xa15c02b7u32,
+            0x7b47f409,
+            0xba1d3330,
+            0x83d2f293,
+            0xbfa4784b,
+            0xcbed606e,
+        ];
+        for value in expected {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    fn test_split_and_fork() {
+        let mut a = Pcg32::new(1, 2);
+        let mut b = a.clone();
+        let mut child_a = a.split();
+        let mut child_b = b.split();
+        assert_eq!(a, b);
+        assert_eq!(child_a.next_u64(), child_b.next_u64());
+        assert_ne!(child_a.next_u64(), a.next_u64());
+
+        let parent = Pcg32::new(1, 2);
+        assert_eq!(parent.fork(3), parent.fork(3));
+        assert_ne!(parent.fork(3).next_u64(), parent.fork(4).next_u64());
+        assert_eq!(parent, Pcg32::new(1, 2));
+    }
+
+    #[test]
+    fn test_for_cell() {
+        let mut seen = std::collections::HashSet::new();
+        for tick in 0..4 {
+            for y in 0..8 {
+                for x in 0..8 {
+                    let mut rng = Pcg32::for_cell(99, tick, Coord(x, y));
+                    let value = rng.next_u64();
+                    assert_eq!(Pcg32::for_cell(99, tick, Coord(x, y)).next_u64(), value);
+                    assert!(seen.insert(value));
+                }
+            }
+        }
+        assert_ne!(
+            Pcg32::for_cell(1, 0, Coord(0, 0)).next_u64(),
+            Pcg32::for_cell(2, 0, Coord(0, 0)).next_u64()
+        );
+    }
+
+    #[test]
+    fn test_below() {
+        let mut rng = Pcg32::new(7, 0);
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[rng.below(6) as usize] += 1;
+        }
+        for count in counts {
+            assert!((800..1200).contains(&count), "{:?}", counts);
+        }
+        assert_eq!(rng.below(1), 0);
+        for _ in 0..100 {
+            let f = rng.next_f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+    }
+}